@@ -1,5 +1,7 @@
 use std::marker::PhantomData;
+use std::ops::{Bound, Range, RangeBounds, RangeFrom, RangeFull, RangeInclusive, RangeTo, RangeToInclusive};
 use super::*;
+use errors::{Candidates, Merge, ParserError};
 
 #[derive(Clone)]
 pub struct Lense<P, F> {
@@ -26,8 +28,8 @@ impl<I, J, P, F> Parser<J> for Lense<P, F> where
     type Error = P::Error;
 
     fn parse(&self, jter: &mut J) -> ParseResult<P::Value, P::Error> {        
-        let mut iter: &mut I = (self.lense)(jter);
-        self.parser.parse(&mut iter)
+        let iter: &mut I = (self.lense)(jter);
+        self.parser.parse(iter)
     }
 }
 
@@ -108,6 +110,37 @@ impl<I, P> Parser<I> for Backtrack<P> where
     }
 }
 
+#[derive(Clone)]
+pub struct Cut<P> {
+    parser: P,
+}
+
+impl<P> Cut<P> {
+    pub fn new(parser: P) -> Cut<P> {
+        Cut {
+            parser,
+        }
+    }
+}
+
+impl<I, P> Parser<I> for Cut<P> where
+    I: Iterator + Clone,
+    P: SizedParser<I>
+{
+    type Value = P::Value;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<P::Value, P::Error> {
+        let mut info = ParseInfo::default();
+        let res = self.parser
+            .parse(iter).record_to(&mut info);
+        match res {
+            Ok(val) => info.commit().ok(val),
+            Err(err) => info.err(err),
+        }
+    }
+}
+
 // Value mapping
 
 #[derive(Clone)]
@@ -365,19 +398,23 @@ impl<P, Q> OrCompose<P, Q> {
 impl<I, P, T, Q> Parser<I> for OrCompose<P, Q> where
     I: Iterator + Clone,
     P: SizedParser<I, Value=T>,
-    Q: SizedParser<I, Value=T>
+    Q: SizedParser<I, Value=T>,
+    Q::Error: From<P::Error>
 {
     type Value = T;
     type Error = Q::Error;
     
     fn parse(&self, iter: &mut I) -> ParseResult<T, Q::Error> {
         let mut info = ParseInfo::default();
-        let res = self.parser
-            .parse(iter).record_to(&mut info)
-            .or_else(|_|
+        let res = match self.parser
+            .parse(iter).record_to(&mut info) {
+            Err(e) if info.committed => Err(e.into()),
+            res => res.or_else(|_|
                 self.other
                 .parse(iter).record_to(&mut info)
-            );
+            ),
+        };
+        info.committed &= res.is_err();
         info.with(res)
     }
 }
@@ -401,6 +438,7 @@ impl<I, P, T, Q, O> Parser<I> for OrElseCompose<P, O> where
     I: Iterator + Clone,
     P: SizedParser<I, Value=T>,
     Q: SizedParser<I, Value=T>,
+    Q::Error: From<P::Error>,
     O: Fn(P::Error) -> Q
 {
     type Value = T;
@@ -408,48 +446,129 @@ impl<I, P, T, Q, O> Parser<I> for OrElseCompose<P, O> where
     
     fn parse(&self, iter: &mut I) -> ParseResult<T, Q::Error> {
         let mut info = ParseInfo::default();
-        let res = self.parser
-            .parse(iter).record_to(&mut info)
-            .or_else(|e|
+        let res = match self.parser
+            .parse(iter).record_to(&mut info) {
+            Err(e) if info.committed => Err(e.into()),
+            res => res.or_else(|e|
                 (self.bind)(e)
                 .parse(iter).record_to(&mut info)
-            );
+            ),
+        };
+        info.committed &= res.is_err();
+        info.with(res)
+    }
+}
+
+// keeps the errors of both branches, see `errors::Merge`
+#[derive(Clone)]
+pub struct OrMerge<P, Q> {
+    parser: P,
+    other: Q,
+}
+
+impl<P, Q> OrMerge<P, Q> {
+    pub fn new(parser: P, other: Q) -> OrMerge<P, Q> {
+        OrMerge {
+            parser,
+            other,
+        }
+    }
+}
+
+impl<I, P, T, E, Q> Parser<I> for OrMerge<P, Q> where
+    I: Iterator + Clone,
+    P: SizedParser<I, Value=T, Error=E>,
+    Q: SizedParser<I, Value=T, Error=E>,
+    E: Merge
+{
+    type Value = T;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<T, E> {
+        let mut info = ParseInfo::default();
+        let res = match self.parser
+            .parse(iter).record_to(&mut info) {
+            Err(e) if info.committed => Err(e),
+            res => res.or_else(|e|
+                self.other
+                .parse(iter).record_to(&mut info)
+                .map_err(|f| e.merge(f))
+            ),
+        };
+        info.committed &= res.is_err();
         info.with(res)
     }
 }
 
 // Vector Combinators
 
+// an item that succeeds without consuming anything would succeed forever, so repetition stops there instead
+// that is always a bug in the grammar, so debug builds panic naming the combinator
+pub(crate) fn no_progress(name: &str) {
+    if cfg!(debug_assertions) {
+        panic!("`{}` parsed an item without consuming any input, which would repeat forever", name)
+    }
+}
+
+// parses items until one fails, only failing itself if that item was committed
+// stops at an item that consumed nothing, see `no_progress`
+pub(crate) fn many_into<I, P>(name: &str, parser: &P, iter: &mut I, info: &mut ParseInfo, values: &mut Vec<P::Value>) -> Result<(), P::Error> where
+    I: Iterator + Clone,
+    P: Parser<I>
+{
+    loop {
+        let mut val_info = ParseInfo::default();
+        let res = parser
+            .parse(iter).record_to(&mut val_info);
+        match res {
+            Ok(_) if val_info.taken == 0 => {
+                no_progress(name);
+                val_info.committed = false;
+                *info += val_info;
+                break Ok(())
+            },
+            Ok(val) => {
+                val_info.committed = false;
+                *info += val_info;
+                values.push(val)
+            },
+            Err(err) => {
+                *info += val_info;
+                break match val_info.committed {
+                    true => Err(err),
+                    false => Ok(()),
+                }
+            },
+        }
+    }
+}
+
 #[derive(Clone)]
-pub struct Many<P, F> {
+pub struct Many<P> {
     parser: P,
-    _f: PhantomData<F>,
 }
 
-impl<P, F> Many<P, F> {
-    pub fn new(parser: P) -> Many<P, F> {
+impl<P> Many<P> {
+    pub fn new(parser: P) -> Many<P> {
         Many {
             parser,
-            _f: PhantomData,
         }
     }
 }
 
-impl<I, P, F> Parser<I> for Many<P, F> where
+impl<I, P> Parser<I> for Many<P> where
     I: Iterator + Clone,
     P: SizedParser<I>
 {
     type Value = Vec<P::Value>;
-    type Error = F;
+    type Error = P::Error;
     
-    fn parse(&self, iter: &mut I) -> ParseResult<Vec<P::Value>, F> {
+    fn parse(&self, iter: &mut I) -> ParseResult<Vec<P::Value>, P::Error> {
         let mut info = ParseInfo::default();
         let mut values = vec![];
-        while let Ok(val) = self.parser
-            .parse(iter).record_to(&mut info) {
-            values.push(val)
-        }
-        info.ok(values)
+        let res = many_into("many", &self.parser, iter, &mut info, &mut values)
+            .map(|_| values);
+        info.with(res)
     }
 }
 
@@ -480,16 +599,240 @@ impl<I, P> Parser<I> for Some<P> where
                 self.parser
                 .parse(iter).record_to(&mut info)?
             ];
-            while let Ok(val) = self.parser
-                .parse(iter).record_to(&mut info) {
-                values.push(val)
-            }
+            info.committed = false;
+            many_into("some", &self.parser, iter, &mut info, &mut values)?;
             Ok(values)
         })();
         info.with(res)
     }
 }
 
+// how many times `Repeat` runs its parser, either exactly or within a range
+pub trait Repetitions {
+    // the least and, if bounded, the most repetitions
+    fn bounds(self) -> (usize, Option<usize>);
+}
+
+impl Repetitions for usize {
+    fn bounds(self) -> (usize, Option<usize>) {
+        (self, Some(self))
+    }
+}
+
+macro range_repetitions($($t:ty),*) {
+    $(
+        impl Repetitions for $t {
+            fn bounds(self) -> (usize, Option<usize>) {
+                let min = match RangeBounds::<usize>::start_bound(&self) {
+                    Bound::Included(&min) => min,
+                    Bound::Excluded(&min) => min + 1,
+                    Bound::Unbounded => 0,
+                };
+                let max = match RangeBounds::<usize>::end_bound(&self) {
+                    Bound::Included(&max) => Some(max),
+                    Bound::Excluded(&max) => Some(max.saturating_sub(1)),
+                    Bound::Unbounded => None,
+                };
+                (min, max)
+            }
+        }
+    )*
+}
+
+range_repetitions!(
+    Range<usize>, RangeInclusive<usize>, RangeFrom<usize>, RangeTo<usize>, RangeToInclusive<usize>, RangeFull
+);
+
+// stops at the maximum without trying for another item
+// an empty range such as `3..3` allows no items, so always succeeds without reading
+#[derive(Clone)]
+pub struct Repeat<P> {
+    parser: P,
+    min: usize,
+    max: Option<usize>,
+}
+
+impl<P> Repeat<P> {
+    pub fn new(parser: P, min: usize, max: Option<usize>) -> Repeat<P> {
+        let (min, max) = match max {
+            Some(max) if max < min => (0, Some(0)),
+            max => (min, max),
+        };
+        Repeat {
+            parser,
+            min,
+            max,
+        }
+    }
+}
+
+impl<I, P> Parser<I> for Repeat<P> where
+    I: Iterator + Clone,
+    P: SizedParser<I>,
+    P::Error: ParserError<I>
+{
+    type Value = Vec<P::Value>;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<Vec<P::Value>, P::Error> {
+        let mut info = ParseInfo::default();
+        let mut values = vec![];
+        while self.max.is_none_or(|max| values.len() < max) {
+            let start = iter.clone();
+            let mut val_info = ParseInfo::default();
+            match self.parser
+                .parse(iter).record_to(&mut val_info) {
+                // only unbounded repetition can loop forever, see `no_progress`
+                Ok(_) if val_info.taken == 0 && self.max.is_none() && values.len() >= self.min => {
+                    no_progress("repeat");
+                    val_info.committed = false;
+                    info += val_info;
+                    break
+                },
+                Ok(val) => {
+                    val_info.committed = false;
+                    info += val_info;
+                    values.push(val)
+                },
+                Err(err) if val_info.committed => {
+                    info += val_info;
+                    return info.err(err)
+                },
+                // errors from within an item are more precise than the count
+                Err(err) if values.len() < self.min => {
+                    let err = match val_info.taken {
+                        0 => err.with_label(&format!("at least {} items", self.min), &start),
+                        _ => err,
+                    };
+                    info += val_info;
+                    return info.err(err)
+                },
+                Err(_) => {
+                    info += val_info;
+                    break
+                },
+            }
+        }
+        info.ok(values)
+    }
+}
+
+// whether a list may, or must, end with a separator, see `SepBy`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Trailing {
+    Forbidden,
+    Optional,
+    Required,
+}
+
+// items separated by `sep`, a separator only being consumed if an item follows it or the trailing policy allows it
+// a failed item or separator is backtracked over unless it was committed
+#[derive(Clone)]
+pub struct SepBy<P, S> {
+    parser: P,
+    sep: S,
+    min: usize,
+    trailing: Trailing,
+}
+
+impl<P, S> SepBy<P, S> {
+    pub fn new(parser: P, sep: S, min: usize, trailing: Trailing) -> SepBy<P, S> {
+        SepBy {
+            parser,
+            sep,
+            min,
+            trailing,
+        }
+    }
+}
+
+impl<I, P, S> Parser<I> for SepBy<P, S> where
+    I: Iterator + Clone,
+    P: SizedParser<I>,
+    S: SizedParser<I, Error=P::Error>
+{
+    type Value = Vec<P::Value>;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<Vec<P::Value>, P::Error> {
+        let mut info = ParseInfo::default();
+        let mut values = vec![];
+        // what was read on a branch that is backtracked over
+        let backtracked = |info: &mut ParseInfo, mut lost: ParseInfo| {
+            lost.taken = 0;
+            lost.committed = false;
+            *info += lost
+        };
+
+        let start = iter.clone();
+        let mut val_info = ParseInfo::default();
+        match self.parser
+            .parse(iter).record_to(&mut val_info) {
+            Ok(val) => {
+                val_info.committed = false;
+                info += val_info;
+                values.push(val)
+            },
+            Err(err) if val_info.committed || self.min > 0 => return val_info.err(err),
+            Err(_) => {
+                *iter = start;
+                backtracked(&mut info, val_info);
+                return info.ok(values)
+            },
+        }
+
+        loop {
+            let before_sep = iter.clone();
+            let mut sep_info = ParseInfo::default();
+            match self.sep
+                .parse(iter).record_to(&mut sep_info) {
+                Ok(_) => (),
+                Err(err) if sep_info.committed || self.trailing == Trailing::Required => {
+                    info += sep_info;
+                    break info.err(err)
+                },
+                Err(_) => {
+                    *iter = before_sep;
+                    backtracked(&mut info, sep_info);
+                    break info.ok(values)
+                },
+            }
+
+            let after_sep = iter.clone();
+            let mut val_info = ParseInfo::default();
+            match self.parser
+                .parse(iter).record_to(&mut val_info) {
+                Ok(_) if sep_info.taken + val_info.taken == 0 => {
+                    no_progress("sep_by");
+                    break info.ok(values)
+                },
+                Ok(val) => {
+                    sep_info.committed = false;
+                    val_info.committed = false;
+                    info += sep_info + val_info;
+                    values.push(val)
+                },
+                Err(err) if val_info.committed => {
+                    info += sep_info + val_info;
+                    break info.err(err)
+                },
+                Err(_) if self.trailing == Trailing::Forbidden => {
+                    *iter = before_sep;
+                    backtracked(&mut info, sep_info + val_info);
+                    break info.ok(values)
+                },
+                Err(_) => {
+                    *iter = after_sep;
+                    sep_info.committed = false;
+                    info += sep_info;
+                    backtracked(&mut info, val_info);
+                    break info.ok(values)
+                },
+            }
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Least<P, Q> {
     parser: P,
@@ -508,7 +851,8 @@ impl<P, Q> Least<P, Q> {
 impl<I, P, Q> Parser<I> for Least<P, Q> where
     I: Iterator + Clone,
     P: SizedParser<I>,
-    Q: SizedParser<I>
+    Q: SizedParser<I>,
+    Q::Error: From<P::Error>
 {
     type Value = (Vec<P::Value>, Q::Value);
     type Error = Q::Error;
@@ -517,14 +861,38 @@ impl<I, P, Q> Parser<I> for Least<P, Q> where
         let mut info = ParseInfo::default();
         let mut values = vec![];
         loop {
+            let mut until_info = ParseInfo::default();
+            let mut val_info = ParseInfo::default();
             match self.until
-                .parse(iter).record_to(&mut info) {
-                Ok(u) => break info.ok((values, u)),
-                Err(err) => match self.parser
-                    .parse(iter).record_to(&mut info) {
-                    Ok(val) => values.push(val),
-                    Err(_) => break info.err(err)
-                }
+                .parse(iter).record_to(&mut until_info) {
+                Ok(u) => {
+                    until_info.committed = false;
+                    info += until_info;
+                    break info.ok((values, u))
+                },
+                Err(err) if until_info.committed => {
+                    info += until_info;
+                    break info.err(err)
+                },
+                Err(err) => {
+                    info += until_info;
+                    match self.parser
+                        .parse(iter).record_to(&mut val_info) {
+                        Ok(val) => {
+                            val_info.committed = false;
+                            info += val_info;
+                            values.push(val)
+                        },
+                        Err(e) if val_info.committed => {
+                            info += val_info;
+                            break info.err(e.into())
+                        },
+                        Err(_) => {
+                            info += val_info;
+                            break info.err(err)
+                        },
+                    }
+                },
             }
         }
     }
@@ -548,7 +916,8 @@ impl<P, Q> Most<P, Q> {
 impl<I, P, Q> Parser<I> for Most<P, Q> where
     I: Iterator + Clone,
     P: SizedParser<I>,
-    Q: SizedParser<I>
+    Q: SizedParser<I>,
+    P::Error: From<Q::Error>
 {
     type Value = (Vec<P::Value>, Q::Value);
     type Error = P::Error;
@@ -565,10 +934,20 @@ impl<I, P, Q> Parser<I> for Most<P, Q> where
             match self.parser
                 .parse(&mut child).record_to(&mut val_info) {
                 Ok(val) => {
+                    val_info.committed = false;
                     values_info.push(val_info);
                     stack.push(child);
                     values.push(val)
                 },
+                // committed, so neither backtracking to an earlier end nor trying `until`
+                Err(err) if val_info.committed => {
+                    for i in values_info {
+                        info += i;
+                    };
+                    info += val_info;
+                    *iter = child;
+                    return info.err(err)
+                },
                 Err(err) => {
                     end_info += val_info;
                     end_info.taken = 0;
@@ -578,14 +957,21 @@ impl<I, P, Q> Parser<I> for Most<P, Q> where
         };
         let res = loop {
             let mut parent = stack.pop().unwrap();
-            match self.until
-                .parse(&mut parent).record_to(&mut end_info) {
+            let mut until_info = ParseInfo::default();
+            let until_res = self.until
+                .parse(&mut parent).record_to(&mut until_info);
+            end_info += until_info;
+            match until_res {
                 Ok(u) => {
                     *iter = parent;
                     break Ok((values, u))
                 },
+                Err(e) if until_info.committed => {
+                    *iter = parent;
+                    break Err(e.into())
+                },
                 Err(_) => {
-                    if let None = values.pop() {
+                    if values.pop().is_none() {
                         *iter = parent;
                         break Err(err)
                     }
@@ -599,6 +985,228 @@ impl<I, P, Q> Parser<I> for Most<P, Q> where
             info += i;
         };
         info += end_info;
+        info.committed &= res.is_err();
+        info.with(res)
+    }
+}
+
+// Context
+
+// names what was expected when the parser fails without consuming anything
+#[derive(Clone)]
+pub struct Label<P> {
+    parser: P,
+    label: String,
+}
+
+impl<P> Label<P> {
+    pub fn new(parser: P, label: String) -> Label<P> {
+        Label {
+            parser,
+            label,
+        }
+    }
+}
+
+impl<I, P> Parser<I> for Label<P> where
+    I: Iterator + Clone,
+    P: SizedParser<I>,
+    P::Error: ParserError<I>
+{
+    type Value = P::Value;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<P::Value, P::Error> {
+        let mut info = ParseInfo::default();
+        let start = iter.clone();
+        let res = self.parser
+            .parse(iter).record_to(&mut info)
+            .map_err(|err| match info.taken {
+                0 => err.with_label(&self.label, &start),
+                _ => err,
+            });
+        info.with(res)
+    }
+}
+
+// wraps any failure with where it happened, stacking as contexts nest
+#[derive(Clone)]
+pub struct Context<P> {
+    parser: P,
+    label: String,
+}
+
+impl<P> Context<P> {
+    pub fn new(parser: P, label: String) -> Context<P> {
+        Context {
+            parser,
+            label,
+        }
+    }
+}
+
+impl<I, P> Parser<I> for Context<P> where
+    I: Iterator + Clone,
+    P: SizedParser<I>,
+    P::Error: ParserError<I>
+{
+    type Value = P::Value;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<P::Value, P::Error> {
+        let mut info = ParseInfo::default();
+        let start = iter.clone();
+        let res = self.parser
+            .parse(iter).record_to(&mut info)
+            .map_err(|err| err.with_context(&self.label, &start));
+        info.with(res)
+    }
+}
+
+// on failure reads the word that was there with `word` and suggests the nearest word the error expected
+// usually wraps a choice between keywords, see `errors::closest`
+#[derive(Clone)]
+pub struct Suggest<P, W> {
+    parser: P,
+    word: W,
+}
+
+impl<P, W> Suggest<P, W> {
+    pub fn new(parser: P, word: W) -> Suggest<P, W> {
+        Suggest {
+            parser,
+            word,
+        }
+    }
+}
+
+impl<I, P, W> Parser<I> for Suggest<P, W> where
+    I: Iterator<Item=char> + Clone,
+    P: SizedParser<I>,
+    P::Error: ParserError<I> + Candidates,
+    W: SizedParser<I, Value=String>
+{
+    type Value = P::Value;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<P::Value, P::Error> {
+        let mut info = ParseInfo::default();
+        let start = iter.clone();
+        match self.parser
+            .parse(iter).record_to(&mut info) {
+            Ok(val) => info.ok(val),
+            Err(err) => {
+                let mut read = ParseInfo::default();
+                let found = self.word
+                    .parse(&mut start.clone()).record_to(&mut read);
+                info.read = std::cmp::max(info.read, read.read);
+                let candidates = err.candidates();
+                let suggestion = found.ok().and_then(|found| {
+                    errors::closest(&found, candidates.iter().map(String::as_str))
+                        .map(|suggestion| (found.chars().collect(), suggestion.chars().collect()))
+                });
+                match suggestion {
+                    Some((found, suggestion)) => info.err(err.with_suggestion(found, suggestion, &start)),
+                    None => info.err(err),
+                }
+            }
+        }
+    }
+}
+
+// Positions
+
+#[derive(Clone)]
+pub struct Spanned<P> {
+    parser: P,
+}
+
+impl<P> Spanned<P> {
+    pub fn new(parser: P) -> Spanned<P> {
+        Spanned {
+            parser,
+        }
+    }
+}
+
+impl<I, P> Parser<I> for Spanned<P> where
+    I: Iterator + Clone + input::Positioned,
+    P: SizedParser<I>
+{
+    type Value = (P::Value, input::Span<I::Pos>);
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<(P::Value, input::Span<I::Pos>), P::Error> {
+        let mut info = ParseInfo::default();
+        let start = iter.pos();
+        let res = self.parser
+            .parse(iter).record_to(&mut info)
+            .map(|val| (val, input::Span::new(start, iter.pos())));
+        info.with(res)
+    }
+}
+
+// Recognition
+
+#[derive(Clone)]
+pub struct Recognize<P, C> {
+    parser: P,
+    _c: PhantomData<C>,
+}
+
+impl<P, C> Recognize<P, C> {
+    pub fn new(parser: P) -> Recognize<P, C> {
+        Recognize {
+            parser,
+            _c: PhantomData,
+        }
+    }
+}
+
+impl<I, P, C> Parser<I> for Recognize<P, C> where
+    I: Iterator + Clone,
+    P: SizedParser<I>,
+    C: FromIterator<I::Item>
+{
+    type Value = C;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<C, P::Error> {
+        let mut info = ParseInfo::default();
+        let start = iter.clone();
+        let res = self.parser
+            .parse(iter).record_to(&mut info)
+            .map(|_| start.take(info.taken).collect());
+        info.with(res)
+    }
+}
+
+#[derive(Clone)]
+pub struct RecognizeSlice<P> {
+    parser: P,
+}
+
+impl<P> RecognizeSlice<P> {
+    pub fn new(parser: P) -> RecognizeSlice<P> {
+        RecognizeSlice {
+            parser,
+        }
+    }
+}
+
+impl<I, P> Parser<I> for RecognizeSlice<P> where
+    I: Iterator + Clone + input::AsSlice,
+    P: SizedParser<I>
+{
+    type Value = I::Slice;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<I::Slice, P::Error> {
+        let mut info = ParseInfo::default();
+        let start = iter.clone();
+        let res = self.parser
+            .parse(iter).record_to(&mut info)
+            .map(|_| start.slice_taken(info.taken));
         info.with(res)
     }
 }
@@ -635,6 +1243,8 @@ impl<I, P, Q> Parser<I> for Continue<P, Q> where
         let res = self.recover
             .parse(iter).record_to(&mut info)
             .map(|_| inner_res);
+        // a recovered failure no longer needs to stop backtracking
+        info.committed &= res.is_err();
         info.with(res)
     }
 }
@@ -671,12 +1281,244 @@ impl<I, P, Q> Parser<I> for Recover<P, Q> where
                 let res = self.recover
                     .parse(iter).record_to(&mut info)
                     .map(|_| Err(err));
+                info.committed &= res.is_err();
+                info.with(res)
+            }
+        }
+    }
+}
+
+// on failure skips ahead with `skip`, standing in `placeholder` for the value and keeping the error alongside
+#[derive(Clone)]
+pub struct RecoverUntil<P, Q, V> {
+    parser: P,
+    skip: Q,
+    placeholder: V,
+}
+
+impl<P, Q, V> RecoverUntil<P, Q, V> {
+    pub fn new(parser: P, skip: Q, placeholder: V) -> RecoverUntil<P, Q, V> {
+        RecoverUntil {
+            parser,
+            skip,
+            placeholder,
+        }
+    }
+}
+
+impl<I, P, Q> Parser<I> for RecoverUntil<P, Q, P::Value> where
+    I: Iterator + Clone,
+    P: SizedParser<I>,
+    P::Value: Clone,
+    Q: SizedParser<I, Value=()>
+{
+    type Value = (P::Value, Option<P::Error>);
+    type Error = Q::Error;
+    
+    fn parse(&self, iter: &mut I) -> ParseResult<(P::Value, Option<P::Error>), Q::Error> {
+        let mut info = ParseInfo::default();
+        match self.parser
+            .parse(iter).record_to(&mut info) {
+            Ok(val) => info.ok((val, None)),
+            Err(err) => {
+                let res = self.skip
+                    .parse(iter).record_to(&mut info)
+                    .map(|_| (self.placeholder.clone(), Some(err)));
+                info.committed &= res.is_err();
                 info.with(res)
             }
         }
     }
 }
 
+// as `Continue`, but the failure is reported to the input rather than returned in the value
+#[derive(Clone)]
+pub struct ContinueReported<P, Q> {
+    parser: P,
+    recover: Q,
+}
+
+impl<P, Q> ContinueReported<P, Q> {
+    pub fn new(parser: P, recover: Q) -> ContinueReported<P, Q> {
+        ContinueReported {
+            parser,
+            recover,
+        }
+    }
+}
+
+impl<I, P, Q> Parser<I> for ContinueReported<P, Q> where
+    I: Iterator + Clone + input::DiagnosticSink<P::Error>,
+    P: SizedParser<I>,
+    Q: SizedParser<I, Value=()>
+{
+    type Value = Option<P::Value>;
+    type Error = Q::Error;
+    
+    fn parse(&self, iter: &mut I) -> ParseResult<Option<P::Value>, Q::Error> {
+        let mut info = ParseInfo::default();
+        let inner_res = self.parser
+            .parse(iter).record_to(&mut info);
+        let res = self.recover
+            .parse(iter).record_to(&mut info)
+            .map(|_| match inner_res {
+                Ok(val) => Some(val),
+                Err(err) => {
+                    iter.report(report::Severity::Error, err);
+                    None
+                }
+            });
+        info.committed &= res.is_err();
+        info.with(res)
+    }
+}
+
+// as `Recover`, but the failure is reported to the input rather than returned in the value
+#[derive(Clone)]
+pub struct RecoverReported<P, Q> {
+    parser: P,
+    recover: Q,
+}
+
+impl<P, Q> RecoverReported<P, Q> {
+    pub fn new(parser: P, recover: Q) -> RecoverReported<P, Q> {
+        RecoverReported {
+            parser,
+            recover,
+        }
+    }
+}
+
+impl<I, P, Q> Parser<I> for RecoverReported<P, Q> where
+    I: Iterator + Clone + input::DiagnosticSink<P::Error>,
+    P: SizedParser<I>,
+    Q: SizedParser<I, Value=()>
+{
+    type Value = Option<P::Value>;
+    type Error = Q::Error;
+    
+    fn parse(&self, iter: &mut I) -> ParseResult<Option<P::Value>, Q::Error> {
+        let mut info = ParseInfo::default();
+        match self.parser
+            .parse(iter).record_to(&mut info) {
+            Ok(res) => info.ok(Some(res)),
+            Err(err) => {
+                let res = self.recover
+                    .parse(iter).record_to(&mut info);
+                if res.is_ok() {
+                    iter.report(report::Severity::Error, err)
+                }
+                info.committed &= res.is_err();
+                info.with(res.map(|_| None))
+            }
+        }
+    }
+}
+
+// repairs a failure where it happened, deleting the token in the way and parsing again, see `input::Repairable`
+// failing that, keeps what was consumed and takes `inserted` as the value, as if the missing tokens were there
+// each repair is reported to the input, a committed failure is left alone
+#[derive(Clone)]
+pub struct Repair<P, V> {
+    parser: P,
+    inserted: V,
+}
+
+impl<P, V> Repair<P, V> {
+    pub fn new(parser: P, inserted: V) -> Repair<P, V> {
+        Repair {
+            parser,
+            inserted,
+        }
+    }
+}
+
+impl<I, P> Parser<I> for Repair<P, P::Value> where
+    I: Iterator + Clone + input::DiagnosticSink<P::Error> + input::DeleteToken,
+    P: SizedParser<I>,
+    P::Value: Clone,
+    P::Error: ParserError<I>
+{
+    type Value = P::Value;
+    type Error = P::Error;
+    
+    fn parse(&self, iter: &mut I) -> ParseResult<P::Value, P::Error> {
+        let mut info = ParseInfo::default();
+        let backup = iter.clone();
+        match self.parser
+            .parse(iter).record_to(&mut info) {
+            Ok(val) => info.ok(val),
+            Err(err) if info.committed => info.err(err),
+            Err(err) => {
+                // the parser failed at the first token it did not consume
+                let failed = info.taken;
+                let mut at = backup.clone();
+                for _ in 0..failed {
+                    at.next();
+                }
+                if let Some(token) = at.clone().next() {
+                    let mut ahead = backup.clone();
+                    ahead.report(report::Severity::Error, P::Error::unexpected(token, &at));
+                    ahead.delete_token(failed);
+                    let mut deleted = ParseInfo::default();
+                    let res = self.parser
+                        .parse(&mut ahead).record_to(&mut deleted);
+                    // counted in the tokens of the input, the deleted one included once reached
+                    let passed = |n: usize| n + (n > failed) as usize;
+                    info.read = std::cmp::max(info.read, passed(deleted.read));
+                    match res {
+                        Ok(val) if deleted.taken > failed => {
+                            *iter = ahead;
+                            return ParseInfo::new(passed(deleted.taken), info.read).ok(val)
+                        },
+                        _ => (),
+                    }
+                }
+                *iter = at;
+                iter.report(report::Severity::Error, err);
+                info.ok(self.inserted.clone())
+            }
+        }
+    }
+}
+
+// reports whatever `f` finds wrong with a value that still parsed, such as a warning
+#[derive(Clone)]
+pub struct Diagnose<P, F> {
+    parser: P,
+    f: F,
+}
+
+impl<P, F> Diagnose<P, F> {
+    pub fn new(parser: P, f: F) -> Diagnose<P, F> {
+        Diagnose {
+            parser,
+            f,
+        }
+    }
+}
+
+impl<I, P, F> Parser<I> for Diagnose<P, F> where
+    I: Iterator + Clone + input::DiagnosticSink<P::Error>,
+    P: SizedParser<I>,
+    F: Fn(&P::Value) -> Option<(report::Severity, P::Error)>
+{
+    type Value = P::Value;
+    type Error = P::Error;
+    
+    fn parse(&self, iter: &mut I) -> ParseResult<P::Value, P::Error> {
+        let mut info = ParseInfo::default();
+        let res = self.parser
+            .parse(iter).record_to(&mut info);
+        if let Ok(val) = &res {
+            if let Some((severity, err)) = (self.f)(val) {
+                iter.report(severity, err)
+            }
+        }
+        info.with(res)
+    }
+}
+
 #[derive(Clone)]
 pub struct AbsorbErr<P> {
     parser: P,
@@ -716,7 +1558,7 @@ pub struct RefParser<'p, P> {
 impl<'p, P> RefParser<'p, P> {
     pub fn new(parser: &'p P) -> RefParser<'p, P> {
         RefParser {
-            parser: parser,
+            parser,
         }
     }
 }
@@ -755,6 +1597,14 @@ impl<'p, I, T, E> ForwardDef<'p, I, T, E> where
     }
 }
 
+impl<'p, I, T, E> Default for ForwardDef<'p, I, T, E> where
+    I: Iterator + Clone
+{
+    fn default() -> ForwardDef<'p, I, T, E> {
+        ForwardDef::new()
+    }
+}
+
 impl<'p, I, T, E> Parser<I> for ForwardDef<'p, I, T, E> where
     I: Iterator + Clone
 {
@@ -788,6 +1638,14 @@ impl<'p, I, T, E> SyncForwardDef<'p, I, T, E> where
     }
 }
 
+impl<'p, I, T, E> Default for SyncForwardDef<'p, I, T, E> where
+    I: Iterator + Clone
+{
+    fn default() -> SyncForwardDef<'p, I, T, E> {
+        SyncForwardDef::new()
+    }
+}
+
 impl<'p, I, T, E> Parser<I> for SyncForwardDef<'p, I, T, E> where
     I: Iterator + Clone
 {
@@ -842,11 +1700,7 @@ impl<I, F, T, E> Parser<I> for Apply0<F, E> where
     type Error = E;
 
     fn parse(&self, _iter: &mut I) -> ParseResult<T, E> {
-        let mut _info = ParseInfo::default();
-        let res = (|| {
-            Ok((self.f)())
-        })();
-        _info.with(res)
+        ParseInfo::default().ok((self.f)())
     }
 }
 
@@ -1008,4 +1862,53 @@ impl<I, F, T, E, P1, P2, P3, P4> Parser<I> for Apply4<F, P1, P2, P3, P4> where
         })();
         info.with(res)
     }
-}
\ No newline at end of file
+}
+
+// runs each parser of a tuple in turn, giving a tuple of their values
+#[derive(Clone)]
+pub struct Seq<T> {
+    parsers: T,
+}
+
+impl<T> Seq<T> {
+    pub fn new(parsers: T) -> Seq<T> {
+        Seq {
+            parsers,
+        }
+    }
+}
+
+macro seq_tuple($($p:ident),+) {
+    #[allow(non_snake_case)]
+    impl<I, E, $($p),+> Parser<I> for Seq<($($p,)+)> where
+        I: Iterator + Clone,
+        $($p: Parser<I, Error=E>),+
+    {
+        type Value = ($($p::Value,)+);
+        type Error = E;
+
+        fn parse(&self, iter: &mut I) -> ParseResult<($($p::Value,)+), E> {
+            let mut info = ParseInfo::default();
+            let ($($p,)+) = &self.parsers;
+            let res = (|| {
+                Ok(($(
+                    $p.parse(iter).record_to(&mut info)?,
+                )+))
+            })();
+            info.with(res)
+        }
+    }
+}
+
+seq_tuple!(P1);
+seq_tuple!(P1, P2);
+seq_tuple!(P1, P2, P3);
+seq_tuple!(P1, P2, P3, P4);
+seq_tuple!(P1, P2, P3, P4, P5);
+seq_tuple!(P1, P2, P3, P4, P5, P6);
+seq_tuple!(P1, P2, P3, P4, P5, P6, P7);
+seq_tuple!(P1, P2, P3, P4, P5, P6, P7, P8);
+seq_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9);
+seq_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10);
+seq_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11);
+seq_tuple!(P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12);
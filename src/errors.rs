@@ -1,14 +1,504 @@
+use std::cmp::Ordering;
+use std::fmt;
+use super::input::{FailureSink, Positioned};
 
-pub struct BranchInternalError<Pos, Tokens>(String, ParseError<Pos, Tokens>);
-pub struct BranchEntryError<Tokens>(String, Tokens);
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BranchInternalError<Pos, Tokens>(pub String, pub ParseError<Pos, Tokens>);
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BranchEntryError<Tokens>(pub String, pub Tokens);
 
 pub type BranchError<Pos, Tokens> = Result<BranchInternalError<Pos, Tokens>, BranchEntryError<Tokens>>;
 
-// when branching the latest internal error will be given else a collection of branch entry errors will be given
+// alternatives are combined by `merge`, which `or_merge` uses: the furthest error wins, expecting errors at the
+// same position join into a branching error and anything else is bundled
+// `or_compose` keeps only the error of the last alternative
 
+#[derive(Clone, PartialEq, Eq, Debug)]
 pub enum ParseError<Pos, Tokens> {
     ContextualisedError(Box<BranchInternalError<Pos, Tokens>>, Pos),
     BranchingError(Vec<BranchEntryError<Tokens>>, Tokens, Pos),
     ExpectedFound(Tokens, Tokens, Pos),
-    Bundle(Vec<ParseError<Pos, Tokens>>)
-}
\ No newline at end of file
+    Bundle(Vec<ParseError<Pos, Tokens>>),
+    // a message from a parser that read the tokens but could not accept them, such as a number out of range
+    Invalid(String, Tokens, Pos),
+    // an expecting error with a near miss of what was found, see `closest`
+    Suggestion(Box<ParseError<Pos, Tokens>>, Tokens),
+}
+
+impl<Pos, Tokens> BranchInternalError<Pos, Tokens> {
+    pub fn new(label: impl Into<String>, err: ParseError<Pos, Tokens>) -> BranchInternalError<Pos, Tokens> {
+        BranchInternalError(label.into(), err)
+    }
+
+    // `pos` is where the labelled branch was entered
+    pub fn at(self, pos: Pos) -> ParseError<Pos, Tokens> {
+        ParseError::ContextualisedError(Box::new(self), pos)
+    }
+}
+
+impl<Tokens> BranchEntryError<Tokens> {
+    pub fn new(label: impl Into<String>, expected: Tokens) -> BranchEntryError<Tokens> {
+        BranchEntryError(label.into(), expected)
+    }
+}
+
+impl<Pos, Tokens> ParseError<Pos, Tokens> {
+    pub fn expected_found(expected: Tokens, found: Tokens, pos: Pos) -> ParseError<Pos, Tokens> {
+        ParseError::ExpectedFound(expected, found, pos)
+    }
+
+    pub fn branching(entries: Vec<BranchEntryError<Tokens>>, found: Tokens, pos: Pos) -> ParseError<Pos, Tokens> {
+        ParseError::BranchingError(entries, found, pos)
+    }
+
+    pub fn contextualised(label: impl Into<String>, err: ParseError<Pos, Tokens>, pos: Pos) -> ParseError<Pos, Tokens> {
+        BranchInternalError::new(label, err).at(pos)
+    }
+
+    pub fn bundle(errors: Vec<ParseError<Pos, Tokens>>) -> ParseError<Pos, Tokens> {
+        ParseError::Bundle(errors)
+    }
+
+    pub fn invalid(message: impl Into<String>, found: Tokens, pos: Pos) -> ParseError<Pos, Tokens> {
+        ParseError::Invalid(message.into(), found, pos)
+    }
+
+    pub fn suggestion(err: ParseError<Pos, Tokens>, suggestion: Tokens) -> ParseError<Pos, Tokens> {
+        ParseError::Suggestion(Box::new(err), suggestion)
+    }
+
+    // the furthest position the error refers to, a contextualised error is located by its cause
+    pub fn pos(&self) -> Option<&Pos> where
+        Pos: Ord
+    {
+        match self {
+            ParseError::ContextualisedError(err, _) => err.1.pos(),
+            ParseError::Suggestion(err, _) => err.pos(),
+            ParseError::Invalid(_, _, pos) => Some(pos),
+            ParseError::BranchingError(_, _, pos) => Some(pos),
+            ParseError::ExpectedFound(_, _, pos) => Some(pos),
+            ParseError::Bundle(errors) => errors.iter()
+                .filter_map(ParseError::pos)
+                .max(),
+        }
+    }
+
+    // keeps the error that got further, combining the two when they failed at the same position
+    pub fn merge(self, other: ParseError<Pos, Tokens>) -> ParseError<Pos, Tokens> where
+        Pos: Ord,
+        Tokens: PartialEq
+    {
+        match self.pos().cmp(&other.pos()) {
+            Ordering::Greater => return self,
+            Ordering::Less => return other,
+            Ordering::Equal => (),
+        }
+        match (self, other) {
+            (ParseError::Bundle(mut errors), ParseError::Bundle(others)) => {
+                for err in others {
+                    if !errors.contains(&err) {
+                        errors.push(err)
+                    }
+                }
+                ParseError::Bundle(errors)
+            },
+            (ParseError::Bundle(mut errors), err) | (err, ParseError::Bundle(mut errors)) => {
+                if !errors.contains(&err) {
+                    errors.push(err)
+                }
+                ParseError::Bundle(errors)
+            },
+            (err, other) if err.is_expecting() && other.is_expecting() => {
+                let (mut entries, found, pos) = err.into_branching();
+                for entry in other.into_branching().0 {
+                    if !entries.contains(&entry) {
+                        entries.push(entry)
+                    }
+                }
+                ParseError::BranchingError(entries, found, pos)
+            },
+            (err, other) => ParseError::Bundle(vec![err, other]),
+        }
+    }
+
+    // only errors expecting something can join a branching error
+    fn is_expecting(&self) -> bool {
+        matches!(self, ParseError::BranchingError(..) | ParseError::ExpectedFound(..))
+    }
+
+    fn into_branching(self) -> (Vec<BranchEntryError<Tokens>>, Tokens, Pos) {
+        match self {
+            ParseError::BranchingError(entries, found, pos) => (entries, found, pos),
+            ParseError::ExpectedFound(expected, found, pos) => (vec![BranchEntryError(String::new(), expected)], found, pos),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<Tokens> fmt::Display for BranchEntryError<Tokens> where
+    Tokens: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_empty() {
+            write!(f, "`{}`", self.1)
+        } else {
+            write!(f, "{}", self.0)
+        }
+    }
+}
+
+// an empty token sequence can only have been found at the end of the input
+struct Found<'a, Tokens>(&'a Tokens);
+
+impl<'a, Tokens> fmt::Display for Found<'a, Tokens> where
+    Tokens: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let found = self.0.to_string();
+        if found.is_empty() {
+            write!(f, "end of input")
+        } else {
+            write!(f, "`{}`", found)
+        }
+    }
+}
+
+impl<Pos, Tokens> ParseError<Pos, Tokens> where
+    Tokens: fmt::Display
+{
+    // what was expected where the error happened, nothing for a bundle or an unexpected token
+    pub fn expectation(&self) -> Option<String> {
+        match self {
+            ParseError::ContextualisedError(err, _) => err.1.expectation(),
+            ParseError::Suggestion(err, _) => err.expectation(),
+            ParseError::Invalid(..) => None,
+            ParseError::BranchingError(entries, _, _) => match entries.as_slice() {
+                [] => None,
+                [entry] => Some(format!("expected {}", entry)),
+                entries => {
+                    let entries: Vec<String> = entries.iter()
+                        .map(BranchEntryError::to_string)
+                        .collect();
+                    Some(format!("expected one of {}", entries.join(", ")))
+                },
+            },
+            ParseError::ExpectedFound(expected, _, _) => Some(format!("expected `{}`", expected)),
+            ParseError::Bundle(_) => None,
+        }
+    }
+
+    // the error without its position or context, as reports place those themselves
+    pub fn message(&self) -> String {
+        match self {
+            ParseError::ContextualisedError(err, _) => err.1.message(),
+            ParseError::Suggestion(err, suggestion) => format!("{}, did you mean `{}`?", err.message(), suggestion),
+            ParseError::Invalid(message, _, _) => message.clone(),
+            ParseError::BranchingError(_, found, _) | ParseError::ExpectedFound(_, found, _) => match self.expectation() {
+                Some(expectation) => format!("{}, found {}", expectation, Found(found)),
+                None => format!("unexpected {}", Found(found)),
+            },
+            ParseError::Bundle(errors) => {
+                let errors: Vec<String> = errors.iter()
+                    .map(ParseError::message)
+                    .collect();
+                errors.join("; ")
+            },
+        }
+    }
+}
+
+impl<Pos, Tokens> fmt::Display for ParseError<Pos, Tokens> where
+    Pos: fmt::Display,
+    Tokens: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::ContextualisedError(err, pos) => write!(f, "{}, in {} at {}", err.1, err.0, pos),
+            ParseError::BranchingError(_, _, pos) | ParseError::ExpectedFound(_, _, pos) | ParseError::Invalid(_, _, pos) => write!(f, "{} at {}", self.message(), pos),
+            ParseError::Suggestion(err, suggestion) => write!(f, "{}, did you mean `{}`?", err, suggestion),
+            ParseError::Bundle(errors) => {
+                for (i, err) in errors.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, "; ")?
+                    }
+                    write!(f, "{}", err)?
+                }
+                Ok(())
+            },
+        }
+    }
+}
+
+impl<Pos, Tokens> std::error::Error for ParseError<Pos, Tokens> where
+    Pos: fmt::Debug + fmt::Display,
+    Tokens: fmt::Debug + fmt::Display
+{}
+
+// Suggestions
+
+// the optimal string alignment distance, counting a swap of neighbouring chars as one edit
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // three rows of the distance table, two back being needed for swaps
+    let mut before: Vec<usize> = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut row = vec![i; b.len() + 1];
+        for j in 1..=b.len() {
+            let cost = (a[i - 1] != b[j - 1]) as usize;
+            row[j] = (prev[j] + 1)
+                .min(row[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                row[j] = row[j].min(before[j - 2] + 1)
+            }
+        }
+        before = std::mem::replace(&mut prev, row);
+    }
+    prev[b.len()]
+}
+
+// the candidate nearest to `found`, if any is within a third of its length, the first winning ties
+pub fn closest<'a, C>(found: &str, candidates: C) -> Option<&'a str> where
+    C: IntoIterator<Item=&'a str>
+{
+    let limit = std::cmp::max(1, found.chars().count() / 3);
+    candidates.into_iter()
+        .map(|candidate| (edit_distance(found, candidate), candidate))
+        .filter(|(distance, _)| (1..=limit).contains(distance))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// errors that can keep what each of several alternatives expected, see `or_merge`
+pub trait Merge {
+    fn merge(self, other: Self) -> Self;
+}
+
+impl<Pos, Tokens> Merge for ParseError<Pos, Tokens> where
+    Pos: Ord,
+    Tokens: PartialEq
+{
+    fn merge(self, other: ParseError<Pos, Tokens>) -> ParseError<Pos, Tokens> {
+        ParseError::merge(self, other)
+    }
+}
+
+// errors that can name the words they expected, for `Suggest` to pick the nearest of
+pub trait Candidates {
+    fn candidates(&self) -> Vec<String>;
+}
+
+// labelled entries name a category rather than a word, so are left out
+impl<Pos, Tokens> Candidates for ParseError<Pos, Tokens> where
+    Tokens: fmt::Display
+{
+    fn candidates(&self) -> Vec<String> {
+        match self {
+            ParseError::ContextualisedError(err, _) => err.1.candidates(),
+            ParseError::Suggestion(err, _) => err.candidates(),
+            ParseError::BranchingError(entries, _, _) => entries.iter()
+                .filter(|entry| entry.0.is_empty())
+                .map(|entry| entry.1.to_string())
+                .collect(),
+            ParseError::ExpectedFound(expected, _, _) => vec![expected.to_string()],
+            ParseError::Invalid(..) => vec![],
+            ParseError::Bundle(errors) => errors.iter()
+                .flat_map(ParseError::candidates)
+                .collect(),
+        }
+    }
+}
+
+// Error construction
+
+// lets primitives and combinators build errors for whatever error type the parser is using
+// `at` is the input positioned at the offending token
+pub trait ParserError<I>: Sized where
+    I: Iterator + Clone
+{
+    fn expected(expected: Vec<I::Item>, found: I::Item, at: &I) -> Self;
+
+    fn unexpected(found: I::Item, at: &I) -> Self;
+
+    // an empty `expected` means anything would have done
+    fn unexpected_eof(expected: Vec<I::Item>, at: &I) -> Self;
+
+    fn expected_one_of(set: Vec<Vec<I::Item>>, found: Option<I::Item>, at: &I) -> Self where
+        I::Item: Clone
+    {
+        set.into_iter()
+            .map(|expected| match found.clone() {
+                Some(found) => Self::expected(expected, found, at),
+                None => Self::unexpected_eof(expected, at),
+            })
+            // by default the latest error wins, as with `or_compose`
+            .last()
+            .unwrap_or_else(|| match found {
+                Some(found) => Self::unexpected(found, at),
+                None => Self::unexpected_eof(vec![], at),
+            })
+    }
+
+    // `at` is where the labelled parser started, only called if it failed without consuming anything
+    fn with_label(self, _label: &str, _at: &I) -> Self {
+        self
+    }
+
+    // `at` is where the labelled parser started
+    fn with_context(self, _label: &str, _at: &I) -> Self {
+        self
+    }
+
+    // `found` is the whole word read at `at`, where the failed parser started
+    fn with_suggestion(self, _found: Vec<I::Item>, _suggestion: Vec<I::Item>, _at: &I) -> Self {
+        self
+    }
+}
+
+struct Sequence<'a, T>(&'a [T]);
+
+impl<'a, T> fmt::Display for Sequence<'a, T> where
+    T: fmt::Display
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.iter().try_for_each(|token| write!(f, "{}", token))
+    }
+}
+
+impl<I> ParserError<I> for String where
+    I: Iterator + Clone,
+    I::Item: fmt::Display
+{
+    fn expected(expected: Vec<I::Item>, found: I::Item, _at: &I) -> String {
+        format!("expected `{}`, found `{}`", Sequence(&expected), found)
+    }
+
+    fn unexpected(found: I::Item, _at: &I) -> String {
+        format!("unexpected `{}`", found)
+    }
+
+    fn unexpected_eof(expected: Vec<I::Item>, _at: &I) -> String {
+        if expected.is_empty() {
+            "unexpected end of input".into()
+        } else {
+            format!("expected `{}`, found end of input", Sequence(&expected))
+        }
+    }
+
+    fn expected_one_of(set: Vec<Vec<I::Item>>, found: Option<I::Item>, at: &I) -> String where
+        I::Item: Clone
+    {
+        let found = match found {
+            Some(found) if set.is_empty() => return <String as ParserError<I>>::unexpected(found, at),
+            Some(found) => format!("`{}`", found),
+            None => "end of input".into(),
+        };
+        let set: Vec<String> = set.iter()
+            .map(|expected| format!("`{}`", Sequence(expected)))
+            .collect();
+        format!("expected one of {}, found {}", set.join(", "), found)
+    }
+
+    fn with_label(self, label: &str, at: &I) -> String {
+        match at.clone().next() {
+            Some(found) => format!("expected {}, found `{}`", label, found),
+            None => format!("expected {}, found end of input", label),
+        }
+    }
+
+    fn with_context(self, label: &str, _at: &I) -> String {
+        format!("{}, in {}", self, label)
+    }
+
+    fn with_suggestion(self, _found: Vec<I::Item>, suggestion: Vec<I::Item>, _at: &I) -> String {
+        format!("{}, did you mean `{}`?", self, Sequence(&suggestion))
+    }
+}
+
+// errors a parser finds in what it has read, `found` being those tokens and `at` where they start
+// any `From<X>` error is built by conversion, so typed errors such as `numbers::NumberError` keep working
+pub trait FromInvalid<X, I>: Sized where
+    I: Iterator + Clone
+{
+    fn from_invalid(err: X, found: Vec<I::Item>, at: &I) -> Self;
+}
+
+impl<X, I, E> FromInvalid<X, I> for E where
+    I: Iterator + Clone,
+    E: From<X>
+{
+    fn from_invalid(err: X, _found: Vec<I::Item>, _at: &I) -> E {
+        err.into()
+    }
+}
+
+impl<I, Tokens> ParserError<I> for ParseError<I::Pos, Tokens> where
+    I: Iterator + Clone + Positioned + FailureSink<ParseError<I::Pos, Tokens>>,
+    I::Pos: Ord,
+    Tokens: FromIterator<I::Item> + PartialEq
+{
+    fn expected(expected: Vec<I::Item>, found: I::Item, at: &I) -> ParseError<I::Pos, Tokens> {
+        let err = ParseError::ExpectedFound(expected.into_iter().collect(), std::iter::once(found).collect(), at.pos());
+        at.record(&err);
+        err
+    }
+
+    fn unexpected(found: I::Item, at: &I) -> ParseError<I::Pos, Tokens> {
+        let err = ParseError::BranchingError(vec![], std::iter::once(found).collect(), at.pos());
+        at.record(&err);
+        err
+    }
+
+    fn unexpected_eof(expected: Vec<I::Item>, at: &I) -> ParseError<I::Pos, Tokens> {
+        let err = if expected.is_empty() {
+            ParseError::BranchingError(vec![], std::iter::empty().collect(), at.pos())
+        } else {
+            ParseError::ExpectedFound(expected.into_iter().collect(), std::iter::empty().collect(), at.pos())
+        };
+        at.record(&err);
+        err
+    }
+
+    fn expected_one_of(set: Vec<Vec<I::Item>>, found: Option<I::Item>, at: &I) -> ParseError<I::Pos, Tokens> where
+        I::Item: Clone
+    {
+        set.into_iter()
+            .map(|expected| match found.clone() {
+                Some(found) => <Self as ParserError<I>>::expected(expected, found, at),
+                None => <Self as ParserError<I>>::unexpected_eof(expected, at),
+            })
+            .reduce(ParseError::merge)
+            .unwrap_or_else(|| match found {
+                Some(found) => <Self as ParserError<I>>::unexpected(found, at),
+                None => <Self as ParserError<I>>::unexpected_eof(vec![], at),
+            })
+    }
+
+    // errors from further in are more precise than the label, so are left alone
+    fn with_label(self, label: &str, at: &I) -> ParseError<I::Pos, Tokens> {
+        let entry = BranchEntryError::new(label, std::iter::empty().collect());
+        match self {
+            ParseError::ExpectedFound(_, found, pos) | ParseError::BranchingError(_, found, pos) if pos == at.pos() => {
+                ParseError::BranchingError(vec![entry], found, pos)
+            },
+            err => err,
+        }
+    }
+
+    fn with_context(self, label: &str, at: &I) -> ParseError<I::Pos, Tokens> {
+        ParseError::contextualised(label, self, at.pos())
+    }
+
+    // the error is moved back to the start of the word, so it is shown whole
+    fn with_suggestion(self, found: Vec<I::Item>, suggestion: Vec<I::Item>, at: &I) -> ParseError<I::Pos, Tokens> {
+        let found = found.into_iter().collect();
+        let err = match self {
+            ParseError::ExpectedFound(expected, _, _) => ParseError::ExpectedFound(expected, found, at.pos()),
+            ParseError::BranchingError(entries, _, _) => ParseError::BranchingError(entries, found, at.pos()),
+            err => return err,
+        };
+        ParseError::suggestion(err, suggestion.into_iter().collect())
+    }
+}
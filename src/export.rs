@@ -0,0 +1,169 @@
+use std::fmt::Display;
+use super::errors::ParseError;
+use super::input::{Location, Span};
+use super::report::Severity;
+
+// the version of the JSON schema below, bumped on any breaking change
+pub const JSON_VERSION: usize = 1;
+
+// a single located error, bundles being flattened into several
+struct Diagnostic {
+    severity: Severity,
+    message: String,
+    span: Span<Location>,
+    expected: Vec<String>,
+    // `None` at the end of the input
+    found: Option<String>,
+    // innermost first
+    context: Vec<(String, Location)>,
+}
+
+// `context` holds the labels above `err`, outermost first
+fn diagnostics<Tokens>(severity: Severity, err: &ParseError<Location, Tokens>, context: &mut Vec<(String, Location)>, out: &mut Vec<Diagnostic>) where
+    Tokens: Display
+{
+    // the suggestion only shows in the message
+    let leaf = match err {
+        ParseError::Suggestion(err, _) => err.as_ref(),
+        err => err,
+    };
+    let (expected, found, start) = match leaf {
+        ParseError::ContextualisedError(err, location) => {
+            context.push((err.0.clone(), *location));
+            diagnostics(severity, &err.1, context, out);
+            context.pop();
+            return
+        },
+        ParseError::Bundle(errors) => {
+            for err in errors {
+                diagnostics(severity, err, context, out)
+            }
+            return
+        },
+        ParseError::BranchingError(entries, found, start) => {
+            let expected = entries.iter()
+                .map(|entry| match entry.0.is_empty() {
+                    true => entry.1.to_string(),
+                    false => entry.0.clone(),
+                })
+                .collect();
+            (expected, found.to_string(), *start)
+        },
+        ParseError::ExpectedFound(expected, found, start) => (vec![expected.to_string()], found.to_string(), *start),
+        ParseError::Invalid(_, found, start) => (vec![], found.to_string(), *start),
+        ParseError::Suggestion(..) => return diagnostics(severity, leaf, context, out),
+    };
+    let mut end = start;
+    for c in found.chars() {
+        end.advance(&c)
+    }
+    out.push(Diagnostic {
+        severity,
+        message: err.message(),
+        span: Span::new(start, end),
+        expected,
+        found: Some(found).filter(|found| !found.is_empty()),
+        context: context.iter().rev().cloned().collect(),
+    })
+}
+
+fn collect<'e, Tokens, E>(errors: E) -> Vec<Diagnostic> where
+    Tokens: Display + 'e,
+    E: IntoIterator<Item=(Severity, &'e ParseError<Location, Tokens>)>
+{
+    let mut out = vec![];
+    for (severity, err) in errors {
+        diagnostics(severity, err, &mut vec![], &mut out)
+    }
+    out
+}
+
+fn string(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out += "\\\"",
+            '\\' => out += "\\\\",
+            '\n' => out += "\\n",
+            '\r' => out += "\\r",
+            '\t' => out += "\\t",
+            c if (c as u32) < 0x20 => out += &format!("\\u{:04x}", c as u32),
+            c => out.push(c),
+        }
+    }
+    out + "\""
+}
+
+fn list<T, F>(items: &[T], f: F) -> String where
+    F: Fn(&T) -> String
+{
+    let items: Vec<String> = items.iter().map(f).collect();
+    format!("[{}]", items.join(","))
+}
+
+// JSON
+
+fn json_location(location: &Location) -> String {
+    format!(
+        "{{\"index\":{},\"offset\":{},\"line\":{},\"column\":{}}}",
+        location.index, location.offset, location.line, location.column
+    )
+}
+
+// `{"version":1,"diagnostics":[{"severity","message","span":{"start","end"},"expected","found","context":[{"label","start"}]}]}`
+// locations are `{"index","offset","line","column"}`, `found` is null at the end of the input and `context` is innermost first
+pub fn to_json<'e, Tokens, E>(errors: E) -> String where
+    Tokens: Display + 'e,
+    E: IntoIterator<Item=(Severity, &'e ParseError<Location, Tokens>)>
+{
+    let diagnostics = list(&collect(errors), |diagnostic| format!(
+        "{{\"severity\":{},\"message\":{},\"span\":{{\"start\":{},\"end\":{}}},\"expected\":{},\"found\":{},\"context\":{}}}",
+        string(diagnostic.severity.as_str()),
+        string(&diagnostic.message),
+        json_location(&diagnostic.span.start),
+        json_location(&diagnostic.span.end),
+        list(&diagnostic.expected, |expected| string(expected)),
+        diagnostic.found.as_deref().map_or("null".into(), string),
+        list(&diagnostic.context, |(label, start)| format!(
+            "{{\"label\":{},\"start\":{}}}",
+            string(label),
+            json_location(start)
+        ))
+    ));
+    format!("{{\"version\":{},\"diagnostics\":{}}}", JSON_VERSION, diagnostics)
+}
+
+// SARIF
+
+fn sarif_location(file: Option<&str>, span: &Span<Location>, message: Option<&str>) -> String {
+    let artifact = file.map_or(String::new(), |file| format!("\"artifactLocation\":{{\"uri\":{}}},", string(file)));
+    let message = message.map_or(String::new(), |message| format!(",\"message\":{{\"text\":{}}}", string(message)));
+    format!(
+        "{{\"physicalLocation\":{{{}\"region\":{{\"startLine\":{},\"startColumn\":{},\"endLine\":{},\"endColumn\":{},\"byteOffset\":{},\"byteLength\":{}}}}}{}}}",
+        artifact,
+        span.start.line, span.start.column, span.end.line, span.end.column,
+        span.start.offset, span.len(),
+        message
+    )
+}
+
+// a SARIF 2.1.0 log with a single run, contexts becoming related locations
+pub fn to_sarif<'e, Tokens, E>(errors: E, tool: &str, file: Option<&str>) -> String where
+    Tokens: Display + 'e,
+    E: IntoIterator<Item=(Severity, &'e ParseError<Location, Tokens>)>
+{
+    let results = list(&collect(errors), |diagnostic| format!(
+        "{{\"level\":{},\"message\":{{\"text\":{}}},\"locations\":[{}],\"relatedLocations\":{}}}",
+        string(diagnostic.severity.as_str()),
+        string(&diagnostic.message),
+        sarif_location(file, &diagnostic.span, None),
+        list(&diagnostic.context, |(label, start)| {
+            sarif_location(file, &Span::new(*start, *start), Some(&format!("in {}", label)))
+        })
+    ));
+    format!(
+        "{{\"version\":\"2.1.0\",\"$schema\":\"https://json.schemastore.org/sarif-2.1.0.json\",\"runs\":[{{\"tool\":{{\"driver\":{{\"name\":{}}}}},\"results\":{}}}]}}",
+        string(tool),
+        results
+    )
+}
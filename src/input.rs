@@ -0,0 +1,791 @@
+use std::cell::RefCell;
+use std::marker::PhantomData;
+use std::rc::Rc;
+use super::*;
+use errors::ParseError;
+use report::Severity;
+
+// Positions
+
+pub trait Positioned {
+    type Pos: Clone;
+
+    fn pos(&self) -> Self::Pos;
+}
+
+// lines and columns start at 1, index and offset at 0
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct Location {
+    pub index: usize,
+    pub offset: usize,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    pub fn new(index: usize, offset: usize, line: usize, column: usize) -> Location {
+        Location {
+            index,
+            offset,
+            line,
+            column,
+        }
+    }
+
+    pub fn advance<T>(&mut self, token: &T) where
+        T: SourceToken
+    {
+        self.index += 1;
+        self.offset += token.byte_len();
+        if token.is_newline() {
+            self.line += 1;
+            self.column = 1;
+        } else {
+            self.column += 1;
+        }
+    }
+}
+
+impl Default for Location {
+    fn default() -> Location {
+        Location::new(0, 0, 1, 1)
+    }
+}
+
+impl std::fmt::Display for Location {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
+// start inclusive, end exclusive
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Span<Pos> {
+    pub start: Pos,
+    pub end: Pos,
+}
+
+impl<Pos> Span<Pos> {
+    pub fn new(start: Pos, end: Pos) -> Span<Pos> {
+        Span {
+            start,
+            end,
+        }
+    }
+}
+
+impl Span<Location> {
+    pub fn len(&self) -> usize {
+        self.end.offset - self.start.offset
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// tokens that know their size in the source, "\r\n" advances the line once as '\r' is not a newline
+pub trait SourceToken {
+    fn byte_len(&self) -> usize;
+
+    fn is_newline(&self) -> bool;
+}
+
+impl SourceToken for char {
+    fn byte_len(&self) -> usize {
+        self.len_utf8()
+    }
+
+    fn is_newline(&self) -> bool {
+        *self == '\n'
+    }
+}
+
+impl SourceToken for u8 {
+    fn byte_len(&self) -> usize {
+        1
+    }
+
+    fn is_newline(&self) -> bool {
+        *self == b'\n'
+    }
+}
+
+impl<T> SourceToken for &T where
+    T: SourceToken
+{
+    fn byte_len(&self) -> usize {
+        (*self).byte_len()
+    }
+
+    fn is_newline(&self) -> bool {
+        (*self).is_newline()
+    }
+}
+
+// inputs backed by the source itself, letting consumed tokens be borrowed rather than collected
+pub trait AsSlice {
+    type Slice: Copy;
+
+    // everything not yet consumed
+    fn as_slice(&self) -> Self::Slice;
+
+    // the next `taken` tokens
+    fn slice_taken(&self, taken: usize) -> Self::Slice;
+}
+
+impl<'a> AsSlice for std::str::Chars<'a> {
+    type Slice = &'a str;
+
+    fn as_slice(&self) -> &'a str {
+        self.as_str()
+    }
+
+    fn slice_taken(&self, taken: usize) -> &'a str {
+        let rest = self.as_str();
+        let end = rest.char_indices()
+            .nth(taken)
+            .map_or(rest.len(), |(i, _)| i);
+        &rest[..end]
+    }
+}
+
+impl<'a, T> AsSlice for std::slice::Iter<'a, T> {
+    type Slice = &'a [T];
+
+    fn as_slice(&self) -> &'a [T] {
+        self.as_slice()
+    }
+
+    fn slice_taken(&self, taken: usize) -> &'a [T] {
+        &self.as_slice()[..taken]
+    }
+}
+
+// Inputs
+
+#[derive(Clone, Debug)]
+pub struct Located<I> {
+    iter: I,
+    location: Location,
+}
+
+impl<I> Located<I> {
+    pub fn new(iter: I) -> Located<I> {
+        Located::starting_at(iter, Location::default())
+    }
+
+    pub fn starting_at(iter: I, location: Location) -> Located<I> {
+        Located {
+            iter,
+            location,
+        }
+    }
+
+    pub fn location(&self) -> Location {
+        self.location
+    }
+
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Iterator for Located<I> where
+    I: Iterator,
+    I::Item: SourceToken
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let token = self.iter.next()?;
+        self.location.advance(&token);
+        Some(token)
+    }
+}
+
+impl<I> Positioned for Located<I> {
+    type Pos = Location;
+
+    fn pos(&self) -> Location {
+        self.location
+    }
+}
+
+impl<I> AsSlice for Located<I> where
+    I: AsSlice
+{
+    type Slice = I::Slice;
+
+    fn as_slice(&self) -> I::Slice {
+        self.iter.as_slice()
+    }
+
+    fn slice_taken(&self, taken: usize) -> I::Slice {
+        self.iter.slice_taken(taken)
+    }
+}
+
+// iterates the chars of a borrowed `str`, `Pos` being the byte offset
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StrInput<'a> {
+    source: &'a str,
+    offset: usize,
+}
+
+impl<'a> StrInput<'a> {
+    pub fn new(source: &'a str) -> StrInput<'a> {
+        StrInput {
+            source,
+            offset: 0,
+        }
+    }
+
+    pub fn source(&self) -> &'a str {
+        self.source
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn rest(&self) -> &'a str {
+        &self.source[self.offset..]
+    }
+}
+
+impl<'a> Iterator for StrInput<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.rest().chars().next()?;
+        self.offset += c.len_utf8();
+        Some(c)
+    }
+}
+
+impl<'a> AsSlice for StrInput<'a> {
+    type Slice = &'a str;
+
+    fn as_slice(&self) -> &'a str {
+        self.rest()
+    }
+
+    fn slice_taken(&self, taken: usize) -> &'a str {
+        self.rest().chars().slice_taken(taken)
+    }
+}
+
+impl<'a> Positioned for StrInput<'a> {
+    type Pos = usize;
+
+    fn pos(&self) -> usize {
+        self.offset
+    }
+}
+
+// iterates references into a borrowed slice, `Pos` being the index
+#[derive(PartialEq, Eq, Debug)]
+pub struct SliceInput<'a, T> {
+    source: &'a [T],
+    offset: usize,
+}
+
+impl<'a, T> SliceInput<'a, T> {
+    pub fn new(source: &'a [T]) -> SliceInput<'a, T> {
+        SliceInput {
+            source,
+            offset: 0,
+        }
+    }
+
+    pub fn source(&self) -> &'a [T] {
+        self.source
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    pub fn rest(&self) -> &'a [T] {
+        &self.source[self.offset..]
+    }
+}
+
+// not derived, which would require `T: Clone`
+impl<'a, T> Clone for SliceInput<'a, T> {
+    fn clone(&self) -> SliceInput<'a, T> {
+        *self
+    }
+}
+
+impl<'a, T> Copy for SliceInput<'a, T> {}
+
+impl<'a, T> Iterator for SliceInput<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        let token = self.source.get(self.offset)?;
+        self.offset += 1;
+        Some(token)
+    }
+}
+
+impl<'a, T> AsSlice for SliceInput<'a, T> {
+    type Slice = &'a [T];
+
+    fn as_slice(&self) -> &'a [T] {
+        self.rest()
+    }
+
+    fn slice_taken(&self, taken: usize) -> &'a [T] {
+        &self.rest()[..taken]
+    }
+}
+
+impl<'a, T> Positioned for SliceInput<'a, T> {
+    type Pos = usize;
+
+    fn pos(&self) -> usize {
+        self.offset
+    }
+}
+
+// Failure tracking
+
+// inputs that can be told about every error built at them, see `Furthest`
+// adapters forward to the input they wrap, an input keeping no record implements it with an empty body
+pub trait FailureSink<E> {
+    fn record(&self, _err: &E) {}
+}
+
+impl<I, E> FailureSink<E> for Located<I> where
+    I: FailureSink<E>
+{
+    fn record(&self, err: &E) {
+        self.iter.record(err)
+    }
+}
+
+impl<'a, E> FailureSink<E> for std::str::Chars<'a> {}
+
+impl<'a, E> FailureSink<E> for std::str::CharIndices<'a> {}
+
+impl<'a, T, E> FailureSink<E> for std::slice::Iter<'a, T> {}
+
+impl<T, E> FailureSink<E> for std::vec::IntoIter<T> {}
+
+impl<I, E> FailureSink<E> for std::iter::Copied<I> {}
+
+impl<I, E> FailureSink<E> for std::iter::Cloned<I> {}
+
+impl<'a, E> FailureSink<E> for StrInput<'a> {}
+
+impl<'a, T, E> FailureSink<E> for SliceInput<'a, T> {}
+
+// remembers the furthest error reached by any branch, including those later backtracked over
+// every clone shares the record, so it survives `attempt`, `backtrack` and `most_until`
+pub struct Furthest<I, E> {
+    iter: I,
+    furthest: Rc<RefCell<Option<E>>>,
+}
+
+impl<I, E> Furthest<I, E> {
+    pub fn new(iter: I) -> Furthest<I, E> {
+        Furthest {
+            iter,
+            furthest: Rc::new(RefCell::new(None)),
+        }
+    }
+
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    pub fn furthest(&self) -> Option<E> where
+        E: Clone
+    {
+        self.furthest.borrow().clone()
+    }
+}
+
+impl<I, Pos, Tokens> Furthest<I, ParseError<Pos, Tokens>> where
+    Pos: Ord + Clone,
+    Tokens: PartialEq + Clone
+{
+    // the final error of a parse, replaced by the furthest failure if that got further
+    pub fn deepest(&self, err: ParseError<Pos, Tokens>) -> ParseError<Pos, Tokens> {
+        match self.furthest() {
+            Some(furthest) => furthest.merge(err),
+            None => err,
+        }
+    }
+}
+
+// not derived, which would require `E: Clone`
+impl<I, E> Clone for Furthest<I, E> where
+    I: Clone
+{
+    fn clone(&self) -> Furthest<I, E> {
+        Furthest {
+            iter: self.iter.clone(),
+            furthest: self.furthest.clone(),
+        }
+    }
+}
+
+impl<I, E> Iterator for Furthest<I, E> where
+    I: Iterator
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+}
+
+impl<I, E> Positioned for Furthest<I, E> where
+    I: Positioned
+{
+    type Pos = I::Pos;
+
+    fn pos(&self) -> I::Pos {
+        self.iter.pos()
+    }
+}
+
+impl<I, E> AsSlice for Furthest<I, E> where
+    I: AsSlice
+{
+    type Slice = I::Slice;
+
+    fn as_slice(&self) -> I::Slice {
+        self.iter.as_slice()
+    }
+
+    fn slice_taken(&self, taken: usize) -> I::Slice {
+        self.iter.slice_taken(taken)
+    }
+}
+
+impl<I, Pos, Tokens> FailureSink<ParseError<Pos, Tokens>> for Furthest<I, ParseError<Pos, Tokens>> where
+    Pos: Ord + Clone,
+    Tokens: PartialEq + Clone
+{
+    fn record(&self, err: &ParseError<Pos, Tokens>) {
+        let mut furthest = self.furthest.borrow_mut();
+        *furthest = Some(match furthest.take() {
+            Some(prev) => prev.merge(err.clone()),
+            None => err.clone(),
+        });
+    }
+}
+
+// Diagnostics
+
+// inputs that collect diagnostics which do not stop the parse, see `Diagnostics`
+pub trait DiagnosticSink<E> {
+    fn report(&mut self, severity: Severity, err: E);
+}
+
+// a diagnostic and those reported before it
+struct Reported<E> {
+    severity: Severity,
+    err: E,
+    prev: Option<Rc<Reported<E>>>,
+}
+
+// collects warnings and recovered errors alongside the parse
+// the list is persistent rather than shared, so backtracking also drops what was reported on the abandoned branch
+pub struct Diagnostics<I, E> {
+    iter: I,
+    last: Option<Rc<Reported<E>>>,
+}
+
+impl<I, E> Diagnostics<I, E> {
+    pub fn new(iter: I) -> Diagnostics<I, E> {
+        Diagnostics {
+            iter,
+            last: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+
+    // in the order they were reported
+    pub fn diagnostics(&self) -> Vec<(Severity, E)> where
+        E: Clone
+    {
+        let mut diagnostics = vec![];
+        let mut reported = self.last.as_deref();
+        while let Some(diagnostic) = reported {
+            diagnostics.push((diagnostic.severity, diagnostic.err.clone()));
+            reported = diagnostic.prev.as_deref();
+        }
+        diagnostics.reverse();
+        diagnostics
+    }
+}
+
+// not derived, which would require `E: Clone`
+impl<I, E> Clone for Diagnostics<I, E> where
+    I: Clone
+{
+    fn clone(&self) -> Diagnostics<I, E> {
+        Diagnostics {
+            iter: self.iter.clone(),
+            last: self.last.clone(),
+        }
+    }
+}
+
+impl<I, E> Iterator for Diagnostics<I, E> where
+    I: Iterator
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        self.iter.next()
+    }
+}
+
+impl<I, E> Positioned for Diagnostics<I, E> where
+    I: Positioned
+{
+    type Pos = I::Pos;
+
+    fn pos(&self) -> I::Pos {
+        self.iter.pos()
+    }
+}
+
+impl<I, E> AsSlice for Diagnostics<I, E> where
+    I: AsSlice
+{
+    type Slice = I::Slice;
+
+    fn as_slice(&self) -> I::Slice {
+        self.iter.as_slice()
+    }
+
+    fn slice_taken(&self, taken: usize) -> I::Slice {
+        self.iter.slice_taken(taken)
+    }
+}
+
+impl<I, E, F> FailureSink<F> for Diagnostics<I, E> where
+    I: FailureSink<F>
+{
+    fn record(&self, err: &F) {
+        self.iter.record(err)
+    }
+}
+
+impl<I, E> DiagnosticSink<E> for Diagnostics<I, E> {
+    fn report(&mut self, severity: Severity, err: E) {
+        self.last = Some(Rc::new(Reported {
+            severity,
+            err,
+            prev: self.last.take(),
+        }));
+    }
+}
+
+impl<I, E, F> DiagnosticSink<F> for Furthest<I, E> where
+    I: DiagnosticSink<F>
+{
+    fn report(&mut self, severity: Severity, err: F) {
+        self.iter.report(severity, err)
+    }
+}
+
+impl<I, E> DeleteToken for Diagnostics<I, E> where
+    I: DeleteToken
+{
+    fn delete_token(&mut self, ahead: usize) {
+        self.iter.delete_token(ahead)
+    }
+}
+
+impl<I, E> DeleteToken for Furthest<I, E> where
+    I: DeleteToken
+{
+    fn delete_token(&mut self, ahead: usize) {
+        self.iter.delete_token(ahead)
+    }
+}
+
+// Repair
+
+// inputs that can drop a token ahead, so `Repair` can parse on as if it was never there
+pub trait DeleteToken {
+    fn delete_token(&mut self, ahead: usize);
+}
+
+// drops deleted tokens from every view of the input, including its position and slices
+// it goes outside `Located`, whose position then still counts the deleted token
+#[derive(Clone, Debug)]
+pub struct Repairable<I> {
+    iter: I,
+    // how many tokens remain before the deleted one
+    deleted: Option<usize>,
+}
+
+impl<I> Repairable<I> {
+    pub fn new(iter: I) -> Repairable<I> {
+        Repairable {
+            iter,
+            deleted: None,
+        }
+    }
+
+    pub fn get_ref(&self) -> &I {
+        &self.iter
+    }
+
+    pub fn into_inner(self) -> I {
+        self.iter
+    }
+}
+
+impl<I> Repairable<I> where
+    I: Iterator + Clone
+{
+    // the inner input past a deleted token that is next
+    fn current(&self) -> I {
+        let mut iter = self.iter.clone();
+        if self.deleted == Some(0) {
+            iter.next();
+        }
+        iter
+    }
+}
+
+impl<I> Iterator for Repairable<I> where
+    I: Iterator
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        match self.deleted.take() {
+            Some(0) => {
+                self.iter.next();
+            },
+            Some(ahead) => self.deleted = Some(ahead - 1),
+            None => (),
+        }
+        self.iter.next()
+    }
+}
+
+impl<I> Positioned for Repairable<I> where
+    I: Iterator + Clone + Positioned
+{
+    type Pos = I::Pos;
+
+    fn pos(&self) -> I::Pos {
+        self.current().pos()
+    }
+}
+
+// a slice cannot leave out the deleted token, so one spanning it includes it
+impl<I> AsSlice for Repairable<I> where
+    I: Iterator + Clone + AsSlice
+{
+    type Slice = I::Slice;
+
+    fn as_slice(&self) -> I::Slice {
+        self.current().as_slice()
+    }
+
+    fn slice_taken(&self, taken: usize) -> I::Slice {
+        match self.deleted {
+            Some(ahead) if ahead > 0 && taken > ahead => self.iter.slice_taken(taken + 1),
+            _ => self.current().slice_taken(taken),
+        }
+    }
+}
+
+impl<I> DeleteToken for Repairable<I> {
+    fn delete_token(&mut self, ahead: usize) {
+        self.deleted = Some(ahead);
+    }
+}
+
+impl<I, E> FailureSink<E> for Repairable<I> where
+    I: FailureSink<E>
+{
+    fn record(&self, err: &E) {
+        self.iter.record(err)
+    }
+}
+
+impl<I, E> DiagnosticSink<E> for Repairable<I> where
+    I: DiagnosticSink<E>
+{
+    fn report(&mut self, severity: Severity, err: E) {
+        self.iter.report(severity, err)
+    }
+}
+
+// runs a parser to completion like a compiler front end, returning its result with everything reported on the way
+#[allow(clippy::type_complexity)]
+pub fn parse_with_diagnostics<I, E, P>(parser: &P, iter: I) -> (Result<P::Value, E>, Vec<(Severity, E)>) where
+    I: Iterator + Clone,
+    E: Clone,
+    P: Parser<Diagnostics<I, E>, Error=E>
+{
+    let mut iter = Diagnostics::new(iter);
+    let res = parser.parse(&mut iter).into_result();
+    (res, iter.diagnostics())
+}
+
+// Parsers
+
+#[derive(Clone)]
+pub struct Position<E> {
+    _e: PhantomData<E>,
+}
+
+impl<E> Position<E> {
+    pub fn new() -> Position<E> {
+        Position {
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<E> Default for Position<E> {
+    fn default() -> Position<E> {
+        Position::new()
+    }
+}
+
+impl<I, E> Parser<I> for Position<E> where
+    I: Iterator + Clone + Positioned
+{
+    type Value = I::Pos;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<I::Pos, E> {
+        ParseInfo::default().ok(iter.pos())
+    }
+}
+
+pub fn position<E>() -> Position<E> {
+    Position::new()
+}
@@ -1,12 +1,18 @@
 #![feature(decl_macro)]
-#![feature(never_type)]
+#![cfg_attr(test, feature(never_type))]
 
 #[cfg(test)]
 mod tests;
 pub mod combinators;
 pub mod primitives;
+pub mod strings;
 pub mod errors; 
+pub mod export;
+pub mod input;
 pub mod memo;
+pub mod numbers;
+pub mod report;
+pub mod text;
 
 use combinators::*;
 use primitives::*;
@@ -15,6 +21,8 @@ use primitives::*;
 pub struct ParseInfo {
     pub taken: usize,
     pub read: usize,
+    // set once a `cut` has been passed, a failure is then not backtracked over by choice points
+    pub committed: bool,
 }
 
 impl ParseInfo {
@@ -22,6 +30,14 @@ impl ParseInfo {
         ParseInfo {
             taken,
             read,
+            committed: false,
+        }
+    }
+
+    pub fn commit(self) -> ParseInfo {
+        ParseInfo {
+            committed: true,
+            ..self
         }
     }
 
@@ -43,10 +59,11 @@ impl std::ops::Add for ParseInfo {
     type Output = ParseInfo;
 
     fn add(self, rhs: Self) -> Self::Output {
-        ParseInfo::new(
-            self.taken + rhs.taken,
-            std::cmp::max(self.read, self.taken + rhs.read)
-        )
+        ParseInfo {
+            taken: self.taken + rhs.taken,
+            read: std::cmp::max(self.read, self.taken + rhs.read),
+            committed: self.committed || rhs.committed,
+        }
     }
 }
 
@@ -55,6 +72,7 @@ impl std::ops::AddAssign for ParseInfo {
     fn add_assign(&mut self, rhs: Self) {
         self.read = std::cmp::max(self.read, self.taken + rhs.read);
         self.taken += rhs.taken;
+        self.committed |= rhs.committed;
     }
 }
 
@@ -98,6 +116,89 @@ pub fn fail<T, E>(e: E) -> Fail<T, E> where
     Fail::new(e)
 }
 
+pub fn tag<T, E>(tag: impl IntoIterator<Item=T>) -> Tag<T, E> {
+    Tag::new(tag.into_iter().collect())
+}
+
+pub fn literal<E>(literal: impl Into<String>) -> Literal<E> {
+    Literal::new(literal.into())
+}
+
+pub fn token<T, E>(token: T) -> Token<T, E> {
+    Token::new(token)
+}
+
+pub fn any_token<E>() -> AnyToken<E> {
+    AnyToken::new()
+}
+
+pub fn end_of_input<E>() -> EndOfInput<E> {
+    EndOfInput::new()
+}
+
+pub fn satisfy<F, E>(predicate: F) -> Satisfy<F, E> {
+    Satisfy::new(predicate)
+}
+
+pub fn one_of<T, E>(set: impl IntoIterator<Item=T>) -> OneOf<T, E> {
+    OneOf::new(set.into_iter().collect())
+}
+
+pub fn none_of<T, E>(set: impl IntoIterator<Item=T>) -> NoneOf<T, E> {
+    NoneOf::new(set.into_iter().collect())
+}
+
+pub fn take_while<F, E>(predicate: F) -> TakeWhile<F, E> {
+    TakeWhile::new(predicate)
+}
+
+pub fn skip_while<F, E>(predicate: F) -> SkipWhile<F, E> {
+    SkipWhile::new(predicate)
+}
+
+pub fn take_while1<F, E>(predicate: F) -> TakeWhileMN<F, E> {
+    TakeWhileMN::new(1, usize::MAX, predicate)
+}
+
+pub fn take_while_m_n<F, E>(min: usize, max: usize, predicate: F) -> TakeWhileMN<F, E> {
+    TakeWhileMN::new(min, max, predicate)
+}
+
+pub fn take_until<P>(until: P) -> TakeUntil<P> {
+    TakeUntil::new(until)
+}
+
+pub fn skip_until<T, E>(sync: impl IntoIterator<Item=T>) -> SkipUntil<T, E> {
+    SkipUntil::new(sync.into_iter().collect())
+}
+
+pub fn skip_balanced<T, E>(sync: impl IntoIterator<Item=T>, pairs: impl IntoIterator<Item=(T, T)>) -> SkipBalanced<T, E> {
+    SkipBalanced::new(sync.into_iter().collect(), pairs.into_iter().collect())
+}
+
+// a tuple of up to twelve parsers
+pub fn seq<T>(parsers: T) -> Seq<T> {
+    Seq::new(parsers)
+}
+
+// keeps the value of `p`
+pub fn preceded<O, P>(open: O, p: P) -> AndCompose<O, P> {
+    AndCompose::new(open, p)
+}
+
+pub fn terminated<P, C>(p: P, close: C) -> PreserveAndCompose<P, C> {
+    PreserveAndCompose::new(p, close)
+}
+
+pub fn between<O, P, C>(open: O, p: P, close: C) -> PreserveAndCompose<AndCompose<O, P>, C> {
+    terminated(preceded(open, p), close)
+}
+
+// the same as `between`
+pub fn delimited<O, P, C>(open: O, p: P, close: C) -> PreserveAndCompose<AndCompose<O, P>, C> {
+    between(open, p, close)
+}
+
 pub trait Parser<I> where
     I: Iterator + Clone
 {
@@ -111,6 +212,7 @@ pub trait Parser<I> where
         let backup = iter.clone();
         match self.parse(iter).record_to(&mut info) {
             Ok(val) => info.ok(val),
+            Err(err) if info.committed => info.err(err),
             Err(err) => {
                 *iter = backup;
                 info.taken = 0;
@@ -123,9 +225,11 @@ pub trait Parser<I> where
         let mut info = ParseInfo::default();
         let backup = iter.clone();
         match self.parse(iter).record_to(&mut info) {
+            // a cut within the lookahead consumed nothing once the input is rewound
             Ok(val) => {
                 *iter = backup;
                 info.taken = 0;
+                info.committed = false;
                 info.ok(val)
             },
             Err(err) => info.err(err)
@@ -136,6 +240,7 @@ pub trait Parser<I> where
         let mut info = ParseInfo::default();
         let res = self.parse(&mut iter.clone()).record_to(&mut info);
         info.taken = 0;
+        info.committed = false;
         ParseResult::new(info, res)
     }
 }
@@ -165,11 +270,11 @@ pub trait SizedParser<I>: Parser<I> where
         memo::MemoIf::new(self, handler, predicate)
     }
 
-    fn reference<'p>(&'p self) -> RefParser<'_, Self> {
+    fn reference(&self) -> RefParser<'_, Self> {
         RefParser::new(self)
     }
 
-    fn discard(self) -> Map<Self, impl Fn(Self::Value) -> ()> {
+    fn discard(self) -> Map<Self, impl Fn(Self::Value)> {
         self.map(|_| ())
     }
 
@@ -197,6 +302,11 @@ pub trait SizedParser<I>: Parser<I> where
         Backtrack::new(self)
     }
 
+    // once `self` has succeeded, failures further on are no longer backtracked over
+    fn cut(self) -> Cut<Self> {
+        Cut::new(self)
+    }
+
     // Value mapping
 
     fn map<U, F>(self, f: F) -> Map<Self, F> where
@@ -245,13 +355,22 @@ pub trait SizedParser<I>: Parser<I> where
     }
 
     fn or_compose<F, P>(self, p: P) -> OrCompose<Self, P> where
-        P: SizedParser<I, Value=Self::Value, Error=F>
+        P: SizedParser<I, Value=Self::Value, Error=F>,
+        F: From<Self::Error>
     {
         OrCompose::new(self, p)
     }
 
+    fn or_merge<P>(self, p: P) -> OrMerge<Self, P> where
+        P: SizedParser<I, Value=Self::Value, Error=Self::Error>,
+        Self::Error: errors::Merge
+    {
+        OrMerge::new(self, p)
+    }
+
     fn or_else_compose<F, P, O>(self, o: O) -> OrElseCompose<Self, O> where
         P: SizedParser<I, Value=Self::Value, Error=F>,
+        F: From<Self::Error>,
         O: Fn(Self::Error) -> P
     {
         OrElseCompose::new(self, o)
@@ -259,7 +378,7 @@ pub trait SizedParser<I>: Parser<I> where
 
     // Vector Combinators
 
-    fn many<E>(self) -> Many<Self, E> {
+    fn many(self) -> Many<Self> {
         Many::new(self)
     }
 
@@ -267,19 +386,99 @@ pub trait SizedParser<I>: Parser<I> where
         Some::new(self)
     }
 
+    // exactly `n` times, or any number in a range such as `2..=4`
+    fn repeat(self, times: impl Repetitions) -> Repeat<Self> where
+        Self::Error: errors::ParserError<I>
+    {
+        let (min, max) = times.bounds();
+        Repeat::new(self, min, max)
+    }
+
+    fn at_most(self, max: usize) -> Repeat<Self> where
+        Self::Error: errors::ParserError<I>
+    {
+        Repeat::new(self, 0, Some(max))
+    }
+
+    // no trailing separator is consumed
+    fn sep_by<S>(self, sep: S) -> SepBy<Self, S> where
+        S: SizedParser<I, Error=Self::Error>
+    {
+        SepBy::new(self, sep, 0, Trailing::Forbidden)
+    }
+
+    fn sep_by1<S>(self, sep: S) -> SepBy<Self, S> where
+        S: SizedParser<I, Error=Self::Error>
+    {
+        SepBy::new(self, sep, 1, Trailing::Forbidden)
+    }
+
+    fn sep_end_by<S>(self, sep: S, trailing: Trailing) -> SepBy<Self, S> where
+        S: SizedParser<I, Error=Self::Error>
+    {
+        SepBy::new(self, sep, 0, trailing)
+    }
+
     fn least_until<U, F, P>(self, end: P) -> Least<Self, P> where
-        P: SizedParser<I, Value=U, Error=F>
+        P: SizedParser<I, Value=U, Error=F>,
+        F: From<Self::Error>
     {
         Least::new(self, end)
     }
 
     // already attempts due to creation of stack structure
     fn most_until<U, F, P>(self, end: P) -> Most<Self, P> where
-        P: SizedParser<I, Value=U, Error=F>
+        P: SizedParser<I, Value=U, Error=F>,
+        Self::Error: From<F>
     {
         Most::new(self, end)
     }
 
+    // Context
+
+    fn label(self, label: impl Into<String>) -> Label<Self> where
+        Self::Error: errors::ParserError<I>
+    {
+        Label::new(self, label.into())
+    }
+
+    fn context(self, label: impl Into<String>) -> Context<Self> where
+        Self::Error: errors::ParserError<I>
+    {
+        Context::new(self, label.into())
+    }
+
+    fn suggest<W>(self, word: W) -> Suggest<Self, W> where
+        I: Iterator<Item=char>,
+        W: SizedParser<I, Value=String>,
+        Self::Error: errors::ParserError<I> + errors::Candidates
+    {
+        Suggest::new(self, word)
+    }
+
+    // Positions
+
+    fn spanned(self) -> Spanned<Self> where
+        I: input::Positioned
+    {
+        Spanned::new(self)
+    }
+
+    // Recognition
+
+    fn recognize<C>(self) -> Recognize<Self, C> where
+        C: FromIterator<I::Item>
+    {
+        Recognize::new(self)
+    }
+
+    // borrows from the input instead of collecting
+    fn recognize_slice(self) -> RecognizeSlice<Self> where
+        I: input::AsSlice
+    {
+        RecognizeSlice::new(self)
+    }
+
     // Error recovery
 
     fn continue_with<F, P>(self, p: P) -> Continue<Self, P> where
@@ -294,6 +493,58 @@ pub trait SizedParser<I>: Parser<I> where
         Recover::new(self, p)
     }
 
+    // skips to a token of `sync`, such as ';', on failure
+    #[allow(clippy::type_complexity)]
+    fn recover_until<T>(self, sync: impl IntoIterator<Item=T>, placeholder: Self::Value) -> RecoverUntil<Self, SkipUntil<T, Self::Error>, Self::Value> where
+        I: Iterator<Item=T>,
+        T: PartialEq,
+        Self::Value: Clone
+    {
+        RecoverUntil::new(self, skip_until(sync), placeholder)
+    }
+
+    // as `recover_until`, but skips over whole groups, see `text::BRACKETS`
+    #[allow(clippy::type_complexity)]
+    fn recover_balanced<T>(self, sync: impl IntoIterator<Item=T>, pairs: impl IntoIterator<Item=(T, T)>, placeholder: Self::Value) -> RecoverUntil<Self, SkipBalanced<T, Self::Error>, Self::Value> where
+        I: Iterator<Item=T>,
+        T: PartialEq,
+        Self::Value: Clone
+    {
+        RecoverUntil::new(self, skip_balanced(sync, pairs), placeholder)
+    }
+
+    // the failure goes to the input's diagnostics, see `input::Diagnostics`
+    fn continue_reported<F, P>(self, p: P) -> ContinueReported<Self, P> where
+        I: input::DiagnosticSink<Self::Error>,
+        P: SizedParser<I, Value=(), Error=F>
+    {
+        ContinueReported::new(self, p)
+    }
+
+    fn recover_reported<F, P>(self, p: P) -> RecoverReported<Self, P> where
+        I: input::DiagnosticSink<Self::Error>,
+        P: SizedParser<I, Value=(), Error=F>
+    {
+        RecoverReported::new(self, p)
+    }
+
+    // single token error repair, such as a missing ')' or a stray ','
+    fn repair(self, inserted: Self::Value) -> Repair<Self, Self::Value> where
+        I: input::DiagnosticSink<Self::Error> + input::DeleteToken,
+        Self::Value: Clone,
+        Self::Error: errors::ParserError<I>
+    {
+        Repair::new(self, inserted)
+    }
+
+    fn diagnose<F>(self, f: F) -> Diagnose<Self, F> where
+        I: input::DiagnosticSink<Self::Error>,
+        F: Fn(&Self::Value) -> Option<(report::Severity, Self::Error)>
+    {
+        Diagnose::new(self, f)
+    }
+
+    #[allow(clippy::type_complexity)]
     fn absorb_err<U>(self) -> AbsorbErr<Map<Self, impl Fn(Self::Value) -> Result<U, Self::Error>>> where
         Self::Value: Into<Result<U, Self::Error>>
     {
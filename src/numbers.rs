@@ -0,0 +1,359 @@
+use std::marker::PhantomData;
+use std::num::IntErrorKind;
+use std::str::FromStr;
+use super::*;
+use super::text::{LexError, Scan};
+use errors::{FromInvalid, ParseError};
+use input::{FailureSink, Positioned};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NumberError {
+    NoDigits,
+    Overflow,
+    Invalid,
+    // `NumberFormat::radix` outside 2..=36
+    InvalidRadix,
+}
+
+impl std::fmt::Display for NumberError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NumberError::NoDigits => write!(f, "expected digits"),
+            NumberError::Overflow => write!(f, "number out of range"),
+            NumberError::Invalid => write!(f, "invalid number"),
+            NumberError::InvalidRadix => write!(f, "radix must be between 2 and 36"),
+        }
+    }
+}
+
+impl std::error::Error for NumberError {}
+
+impl<I, Tokens> FromInvalid<NumberError, I> for ParseError<I::Pos, Tokens> where
+    I: Iterator + Clone + Positioned + FailureSink<ParseError<I::Pos, Tokens>>,
+    Tokens: FromIterator<I::Item>
+{
+    fn from_invalid(err: NumberError, found: Vec<I::Item>, at: &I) -> ParseError<I::Pos, Tokens> {
+        let err = ParseError::invalid(err.to_string(), found.into_iter().collect(), at.pos());
+        at.record(&err);
+        err
+    }
+}
+
+// the digits read, or the token where they were expected
+impl LexError for NumberError {
+    fn span(&self, info: &ParseInfo) -> (usize, usize) {
+        (0, info.taken)
+    }
+}
+
+impl From<NumberError> for String {
+    fn from(err: NumberError) -> String {
+        err.to_string()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct NumberFormat {
+    // a leading '+', or '-' when the target is signed
+    pub sign: bool,
+    // `0x`, `0o` and `0b`, integers only
+    pub prefixes: bool,
+    // used when no prefix is given, from 2 to 36, integers only
+    pub radix: u32,
+    // may appear anywhere after the first digit
+    pub separator: Option<char>,
+    // `inf`, `infinity` and `nan` in any case, floats only
+    pub special: bool,
+}
+
+impl NumberFormat {
+    pub const DECIMAL: NumberFormat = NumberFormat {
+        sign: true,
+        prefixes: false,
+        radix: 10,
+        separator: None,
+        special: false,
+    };
+
+    pub const RUST: NumberFormat = NumberFormat {
+        sign: true,
+        prefixes: true,
+        radix: 10,
+        separator: Some('_'),
+        special: false,
+    };
+}
+
+impl Default for NumberFormat {
+    fn default() -> NumberFormat {
+        NumberFormat::DECIMAL
+    }
+}
+
+// Targets
+
+pub trait PrimInt: Sized {
+    const SIGNED: bool;
+
+    fn from_str_radix(src: &str, radix: u32) -> Result<Self, std::num::ParseIntError>;
+}
+
+macro prim_int($($t:ty: $signed:expr),*) {
+    $(
+        impl PrimInt for $t {
+            const SIGNED: bool = $signed;
+
+            fn from_str_radix(src: &str, radix: u32) -> Result<$t, std::num::ParseIntError> {
+                <$t>::from_str_radix(src, radix)
+            }
+        }
+    )*
+}
+
+prim_int!(
+    i8: true, i16: true, i32: true, i64: true, i128: true, isize: true,
+    u8: false, u16: false, u32: false, u64: false, u128: false, usize: false
+);
+
+pub trait PrimFloat: FromStr {
+    fn is_infinite(&self) -> bool;
+}
+
+impl PrimFloat for f32 {
+    fn is_infinite(&self) -> bool {
+        f32::is_infinite(*self)
+    }
+}
+
+impl PrimFloat for f64 {
+    fn is_infinite(&self) -> bool {
+        f64::is_infinite(*self)
+    }
+}
+
+// Lexing
+
+impl<I> Scan<I> where
+    I: Iterator<Item=char> + Clone
+{
+    fn sign(&mut self, negative: bool) {
+        if let Some(c) = self.eat(|c| *c == '+' || (negative && *c == '-')) {
+            self.text.push(c)
+        }
+    }
+
+    fn digits(&mut self, radix: u32, separator: Option<char>) -> usize {
+        let mut count = 0;
+        while let Some(c) = self.eat(|c| c.is_digit(radix) || (count > 0 && Some(*c) == separator)) {
+            if Some(c) != separator {
+                self.text.push(c);
+                count += 1
+            }
+        }
+        count
+    }
+
+    fn prefix(&mut self) -> Option<u32> {
+        let mut radix = None;
+        self.attempt(|scan| {
+            scan.eat(|c| *c == '0').is_some() && {
+                radix = scan.eat(|c| "xXoObB".contains(*c))
+                    .map(|c| match c.to_ascii_lowercase() {
+                        'x' => 16,
+                        'o' => 8,
+                        _ => 2,
+                    });
+                radix.is_some()
+            }
+        });
+        radix
+    }
+
+    fn special(&mut self) -> bool {
+        ["infinity", "inf", "nan"].into_iter().any(|special| self.attempt(|scan| {
+            let found = special.chars().all(|expected|
+                scan.eat(|c| c.eq_ignore_ascii_case(&expected)).is_some()
+            );
+            scan.text.push_str(special);
+            found
+        }))
+    }
+
+    fn float(&mut self, format: &NumberFormat) -> Result<(), NumberError> {
+        if format.sign {
+            self.sign(true)
+        }
+        if format.special && self.special() {
+            return Ok(())
+        }
+        if self.digits(10, format.separator) == 0 {
+            return Err(NumberError::NoDigits)
+        }
+        // a '.' or 'e' is left alone unless digits follow it
+        self.attempt(|scan| {
+            scan.eat(|c| *c == '.').is_some() && {
+                scan.text.push('.');
+                scan.digits(10, format.separator) > 0
+            }
+        });
+        self.attempt(|scan| {
+            scan.eat(|c| *c == 'e' || *c == 'E').is_some() && {
+                scan.text.push('e');
+                scan.sign(true);
+                scan.digits(10, format.separator) > 0
+            }
+        });
+        Ok(())
+    }
+}
+
+// Parsers
+
+#[derive(Clone)]
+pub struct Integer<T, E> {
+    format: NumberFormat,
+    _t: PhantomData<T>,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> Integer<T, E> {
+    pub fn new(format: NumberFormat) -> Integer<T, E> {
+        Integer {
+            format,
+            _t: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for Integer<T, E> where
+    I: Iterator<Item=char> + Clone,
+    T: PrimInt,
+    E: FromInvalid<NumberError, I>
+{
+    type Value = T;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<T, E> {
+        let mut scan = Scan::new(iter.clone());
+        if !(2..=36).contains(&self.format.radix) {
+            return scan.finish(iter, Err(NumberError::InvalidRadix))
+        }
+        if self.format.sign {
+            scan.sign(T::SIGNED)
+        }
+        let radix = self.format.prefixes
+            .then(|| scan.prefix())
+            .flatten()
+            .unwrap_or(self.format.radix);
+        let res = if scan.digits(radix, self.format.separator) == 0 {
+            Err(NumberError::NoDigits)
+        } else {
+            T::from_str_radix(&scan.text, radix)
+                .map_err(|err| match err.kind() {
+                    IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => NumberError::Overflow,
+                    _ => NumberError::Invalid,
+                })
+        };
+        scan.finish(iter, res)
+    }
+}
+
+pub fn integer<T, E>() -> Integer<T, E> {
+    Integer::new(NumberFormat::default())
+}
+
+pub fn integer_with<T, E>(format: NumberFormat) -> Integer<T, E> {
+    Integer::new(format)
+}
+
+#[derive(Clone)]
+pub struct Float<T, E> {
+    format: NumberFormat,
+    _t: PhantomData<T>,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> Float<T, E> {
+    pub fn new(format: NumberFormat) -> Float<T, E> {
+        Float {
+            format,
+            _t: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for Float<T, E> where
+    I: Iterator<Item=char> + Clone,
+    T: PrimFloat,
+    E: FromInvalid<NumberError, I>
+{
+    type Value = T;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<T, E> {
+        let mut scan = Scan::new(iter.clone());
+        let res = scan.float(&self.format)
+            .and_then(|_| T::from_str(&scan.text).map_err(|_| NumberError::Invalid))
+            .and_then(|val| {
+                let written_infinite = scan.text.ends_with("inf") || scan.text.ends_with("infinity");
+                if val.is_infinite() && !written_infinite {
+                    Err(NumberError::Overflow)
+                } else {
+                    Ok(val)
+                }
+            });
+        scan.finish(iter, res)
+    }
+}
+
+pub fn float<T, E>() -> Float<T, E> {
+    Float::new(NumberFormat::default())
+}
+
+pub fn float_with<T, E>(format: NumberFormat) -> Float<T, E> {
+    Float::new(format)
+}
+
+// any `FromStr` target, given the float syntax with separators removed
+#[derive(Clone)]
+pub struct Decimal<T, E> {
+    format: NumberFormat,
+    _t: PhantomData<T>,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> Decimal<T, E> {
+    pub fn new(format: NumberFormat) -> Decimal<T, E> {
+        Decimal {
+            format,
+            _t: PhantomData,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for Decimal<T, E> where
+    I: Iterator<Item=char> + Clone,
+    T: FromStr,
+    E: FromInvalid<NumberError, I>
+{
+    type Value = T;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<T, E> {
+        let mut scan = Scan::new(iter.clone());
+        let res = scan.float(&self.format)
+            .and_then(|_| T::from_str(&scan.text).map_err(|_| NumberError::Invalid));
+        scan.finish(iter, res)
+    }
+}
+
+pub fn decimal<T, E>() -> Decimal<T, E> {
+    Decimal::new(NumberFormat::default())
+}
+
+pub fn decimal_with<T, E>(format: NumberFormat) -> Decimal<T, E> {
+    Decimal::new(format)
+}
@@ -1,5 +1,6 @@
 use std::marker::PhantomData;
 use super::*;
+use errors::ParserError;
 
 #[derive(Clone)]
 pub struct Wrap<T, E> where
@@ -61,4 +62,555 @@ impl<I, T, E> Parser<I> for Fail<T, E> where
     fn parse(&self, _iter: &mut I) -> ParseResult<T, E> {
         ParseInfo::default().err(self.err.clone())
     }
-}
\ No newline at end of file
+}
+
+// reads a single token, only advancing `iter` if it is accepted
+// the rejected token is handed back, `None` meaning the input has ended
+pub(crate) fn next_if<I, F>(iter: &mut I, accept: F) -> Result<I::Item, Option<I::Item>> where
+    I: Iterator + Clone,
+    F: FnOnce(&I::Item) -> bool
+{
+    let mut ahead = iter.clone();
+    match ahead.next() {
+        Some(token) if accept(&token) => {
+            *iter = ahead;
+            Ok(token)
+        },
+        found => Err(found),
+    }
+}
+
+// builds the error for a token rejected by `next_if`, `iter` still being positioned at it
+pub(crate) fn reject<I, E>(expected: Vec<I::Item>, found: Option<I::Item>, iter: &I) -> E where
+    I: Iterator + Clone,
+    E: ParserError<I>
+{
+    match found {
+        Some(found) if expected.is_empty() => E::unexpected(found, iter),
+        Some(found) => E::expected(expected, found, iter),
+        None => E::unexpected_eof(expected, iter),
+    }
+}
+
+// matches each expected token in turn, stopping at the first mismatch
+fn next_all<I, T>(iter: &mut I, expected: T) -> ParseResult<(), Option<I::Item>> where
+    I: Iterator + Clone,
+    T: IntoIterator,
+    I::Item: PartialEq<T::Item>
+{
+    let mut info = ParseInfo::default();
+    for token in expected {
+        let res = next_if(iter, |found| *found == token);
+        if let Err(found) = res {
+            return (info + ParseInfo::new(0, 1)).err(found);
+        }
+        info += ParseInfo::new(1, 1);
+    }
+    info.ok(())
+}
+
+#[derive(Clone)]
+pub struct Tag<T, E> {
+    tag: Vec<T>,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> Tag<T, E> {
+    pub fn new(tag: Vec<T>) -> Tag<T, E> {
+        Tag {
+            tag,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for Tag<T, E> where
+    I: Iterator<Item=T> + Clone,
+    T: PartialEq + Clone,
+    E: ParserError<I>
+{
+    type Value = Vec<T>;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<Vec<T>, E> {
+        let mut info = ParseInfo::default();
+        let res = next_all(iter, self.tag.iter().cloned()).record_to(&mut info)
+            .map(|_| self.tag.clone())
+            .map_err(|found| reject(self.tag.clone(), found, iter));
+        info.with(res)
+    }
+}
+
+#[derive(Clone)]
+pub struct Literal<E> {
+    literal: String,
+    _e: PhantomData<E>,
+}
+
+impl<E> Literal<E> {
+    pub fn new(literal: String) -> Literal<E> {
+        Literal {
+            literal,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, E> Parser<I> for Literal<E> where
+    I: Iterator<Item=char> + Clone,
+    E: ParserError<I>
+{
+    type Value = String;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<String, E> {
+        let mut info = ParseInfo::default();
+        let res = next_all(iter, self.literal.chars()).record_to(&mut info)
+            .map(|_| self.literal.clone())
+            .map_err(|found| reject(self.literal.chars().collect(), found, iter));
+        info.with(res)
+    }
+}
+
+#[derive(Clone)]
+pub struct Token<T, E> {
+    token: T,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> Token<T, E> {
+    pub fn new(token: T) -> Token<T, E> {
+        Token {
+            token,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for Token<T, E> where
+    I: Iterator<Item=T> + Clone,
+    T: PartialEq + Clone,
+    E: ParserError<I>
+{
+    type Value = T;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<T, E> {
+        let res = next_if(iter, |found| *found == self.token)
+            .map_err(|found| reject(vec![self.token.clone()], found, iter));
+        ParseInfo::new(res.is_ok() as usize, 1).with(res)
+    }
+}
+
+#[derive(Clone)]
+pub struct AnyToken<E> {
+    _e: PhantomData<E>,
+}
+
+impl<E> AnyToken<E> {
+    pub fn new() -> AnyToken<E> {
+        AnyToken {
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<E> Default for AnyToken<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, E> Parser<I> for AnyToken<E> where
+    I: Iterator + Clone,
+    E: ParserError<I>
+{
+    type Value = I::Item;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<I::Item, E> {
+        match iter.next() {
+            Some(token) => ParseInfo::new(1, 1).ok(token),
+            None => ParseInfo::new(0, 1).err(E::unexpected_eof(vec![], iter)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct EndOfInput<E> {
+    _e: PhantomData<E>,
+}
+
+impl<E> EndOfInput<E> {
+    pub fn new() -> EndOfInput<E> {
+        EndOfInput {
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<E> Default for EndOfInput<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, E> Parser<I> for EndOfInput<E> where
+    I: Iterator + Clone,
+    E: ParserError<I>
+{
+    type Value = ();
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<(), E> {
+        match iter.clone().next() {
+            None => ParseInfo::new(0, 1).ok(()),
+            Some(found) => ParseInfo::new(0, 1).err(E::unexpected(found, iter)),
+        }
+    }
+}
+
+// Predicates
+
+#[derive(Clone)]
+pub struct Satisfy<F, E> {
+    predicate: F,
+    _e: PhantomData<E>,
+}
+
+impl<F, E> Satisfy<F, E> {
+    pub fn new(predicate: F) -> Satisfy<F, E> {
+        Satisfy {
+            predicate,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, F, E> Parser<I> for Satisfy<F, E> where
+    I: Iterator + Clone,
+    F: Fn(&I::Item) -> bool,
+    E: ParserError<I>
+{
+    type Value = I::Item;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<I::Item, E> {
+        let res = next_if(iter, &self.predicate)
+            .map_err(|found| reject(vec![], found, iter));
+        ParseInfo::new(res.is_ok() as usize, 1).with(res)
+    }
+}
+
+#[derive(Clone)]
+pub struct OneOf<T, E> {
+    set: Vec<T>,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> OneOf<T, E> {
+    pub fn new(set: Vec<T>) -> OneOf<T, E> {
+        OneOf {
+            set,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for OneOf<T, E> where
+    I: Iterator<Item=T> + Clone,
+    T: PartialEq + Clone,
+    E: ParserError<I>
+{
+    type Value = T;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<T, E> {
+        let res = next_if(iter, |found| self.set.contains(found))
+            .map_err(|found| {
+                let set = self.set.iter()
+                    .map(|token| vec![token.clone()])
+                    .collect();
+                E::expected_one_of(set, found, iter)
+            });
+        ParseInfo::new(res.is_ok() as usize, 1).with(res)
+    }
+}
+
+#[derive(Clone)]
+pub struct NoneOf<T, E> {
+    set: Vec<T>,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> NoneOf<T, E> {
+    pub fn new(set: Vec<T>) -> NoneOf<T, E> {
+        NoneOf {
+            set,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for NoneOf<T, E> where
+    I: Iterator<Item=T> + Clone,
+    T: PartialEq,
+    E: ParserError<I>
+{
+    type Value = T;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<T, E> {
+        let res = next_if(iter, |found| !self.set.contains(found))
+            .map_err(|found| reject(vec![], found, iter));
+        ParseInfo::new(res.is_ok() as usize, 1).with(res)
+    }
+}
+#[derive(Clone)]
+pub struct TakeWhile<F, E> {
+    predicate: F,
+    _e: PhantomData<E>,
+}
+
+impl<F, E> TakeWhile<F, E> {
+    pub fn new(predicate: F) -> TakeWhile<F, E> {
+        TakeWhile {
+            predicate,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, F, E> Parser<I> for TakeWhile<F, E> where
+    I: Iterator + Clone,
+    F: Fn(&I::Item) -> bool
+{
+    type Value = Vec<I::Item>;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<Vec<I::Item>, E> {
+        let mut info = ParseInfo::default();
+        let mut values = vec![];
+        while let Ok(token) = next_if(iter, &self.predicate) {
+            info += ParseInfo::new(1, 1);
+            values.push(token)
+        }
+        info += ParseInfo::new(0, 1);
+        info.ok(values)
+    }
+}
+
+// collects nothing, pair with `recognize_slice` to borrow the run instead
+#[derive(Clone)]
+pub struct SkipWhile<F, E> {
+    predicate: F,
+    _e: PhantomData<E>,
+}
+
+impl<F, E> SkipWhile<F, E> {
+    pub fn new(predicate: F) -> SkipWhile<F, E> {
+        SkipWhile {
+            predicate,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, F, E> Parser<I> for SkipWhile<F, E> where
+    I: Iterator + Clone,
+    F: Fn(&I::Item) -> bool
+{
+    type Value = ();
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<(), E> {
+        let mut info = ParseInfo::default();
+        while next_if(iter, &self.predicate).is_ok() {
+            info += ParseInfo::new(1, 1)
+        }
+        info += ParseInfo::new(0, 1);
+        info.ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct TakeWhileMN<F, E> {
+    min: usize,
+    max: usize,
+    predicate: F,
+    _e: PhantomData<E>,
+}
+
+impl<F, E> TakeWhileMN<F, E> {
+    pub fn new(min: usize, max: usize, predicate: F) -> TakeWhileMN<F, E> {
+        TakeWhileMN {
+            min,
+            max,
+            predicate,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, F, E> Parser<I> for TakeWhileMN<F, E> where
+    I: Iterator + Clone,
+    F: Fn(&I::Item) -> bool,
+    E: ParserError<I>
+{
+    type Value = Vec<I::Item>;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<Vec<I::Item>, E> {
+        let mut info = ParseInfo::default();
+        let mut values = vec![];
+        let mut stop = None;
+        // no lookahead is needed once `max` tokens have been taken
+        while values.len() < self.max {
+            match next_if(iter, &self.predicate) {
+                Ok(token) => {
+                    info += ParseInfo::new(1, 1);
+                    values.push(token)
+                },
+                Err(found) => {
+                    info += ParseInfo::new(0, 1);
+                    stop = Some(found);
+                    break
+                },
+            }
+        }
+        // checked after the loop, as `max` may be below `min`
+        if values.len() < self.min {
+            let found = stop.unwrap_or_else(|| iter.clone().next());
+            return info.err(reject(vec![], found, iter));
+        }
+        info.ok(values)
+    }
+}
+
+#[derive(Clone)]
+pub struct TakeUntil<P> {
+    until: P,
+}
+
+impl<P> TakeUntil<P> {
+    pub fn new(until: P) -> TakeUntil<P> {
+        TakeUntil {
+            until,
+        }
+    }
+}
+
+impl<I, P> Parser<I> for TakeUntil<P> where
+    I: Iterator + Clone,
+    P: Parser<I>
+{
+    type Value = Vec<I::Item>;
+    type Error = P::Error;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<Vec<I::Item>, P::Error> {
+        let mut info = ParseInfo::default();
+        let mut values = vec![];
+        loop {
+            match self.until
+                .backtrack_parse(iter).record_to(&mut info) {
+                Ok(_) => break info.ok(values),
+                Err(err) => match iter.next() {
+                    Some(token) => {
+                        info += ParseInfo::new(1, 1);
+                        values.push(token)
+                    },
+                    None => {
+                        info += ParseInfo::new(0, 1);
+                        break info.err(err)
+                    },
+                }
+            }
+        }
+    }
+}
+
+// Synchronisation
+
+// skips to the next token of `sync` without consuming it, or to the end of the input, never failing
+#[derive(Clone)]
+pub struct SkipUntil<T, E> {
+    sync: Vec<T>,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> SkipUntil<T, E> {
+    pub fn new(sync: Vec<T>) -> SkipUntil<T, E> {
+        SkipUntil {
+            sync,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for SkipUntil<T, E> where
+    I: Iterator<Item=T> + Clone,
+    T: PartialEq
+{
+    type Value = ();
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<(), E> {
+        let mut info = ParseInfo::default();
+        while next_if(iter, |token| !self.sync.contains(token)).is_ok() {
+            info += ParseInfo::new(1, 1)
+        }
+        info += ParseInfo::new(0, 1);
+        info.ok(())
+    }
+}
+
+// as `SkipUntil`, but skips whole nested groups given as (open, close) pairs
+// also stops before a closing token that was not opened, so a group around the failure is left to be closed
+#[derive(Clone)]
+pub struct SkipBalanced<T, E> {
+    sync: Vec<T>,
+    pairs: Vec<(T, T)>,
+    _e: PhantomData<E>,
+}
+
+impl<T, E> SkipBalanced<T, E> {
+    pub fn new(sync: Vec<T>, pairs: Vec<(T, T)>) -> SkipBalanced<T, E> {
+        SkipBalanced {
+            sync,
+            pairs,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, T, E> Parser<I> for SkipBalanced<T, E> where
+    I: Iterator<Item=T> + Clone,
+    T: PartialEq
+{
+    type Value = ();
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<(), E> {
+        let mut info = ParseInfo::default();
+        // the closing tokens of the groups currently open, innermost last
+        let mut open: Vec<&T> = vec![];
+        loop {
+            let next = next_if(iter, |token| !open.is_empty() || !(
+                self.sync.contains(token) || self.pairs.iter().any(|(_, close)| close == token)
+            ));
+            match next {
+                Ok(token) => {
+                    info += ParseInfo::new(1, 1);
+                    if open.last() == Some(&&token) {
+                        open.pop();
+                    } else if let Some((_, close)) = self.pairs.iter().find(|(start, _)| *start == token) {
+                        open.push(close)
+                    }
+                },
+                Err(_) => break,
+            }
+        }
+        info += ParseInfo::new(0, 1);
+        info.ok(())
+    }
+}
@@ -0,0 +1,214 @@
+use std::fmt::Display;
+use super::errors::ParseError;
+use super::input::Location;
+
+const RED: &str = "\x1b[1;31m";
+const YELLOW: &str = "\x1b[1;33m";
+const BLUE: &str = "\x1b[1;34m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum Severity {
+    Error,
+    Warning,
+    Note,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Note => "note",
+        }
+    }
+
+    fn colour(&self) -> &'static str {
+        match self {
+            Severity::Error => RED,
+            Severity::Warning => YELLOW,
+            Severity::Note => BLUE,
+        }
+    }
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+// renders located errors as compiler style diagnostics over the source they came from
+#[derive(Clone, Copy, Debug)]
+pub struct Report<'a> {
+    pub source: &'a str,
+    pub file: Option<&'a str>,
+    // ANSI escapes, for terminals only
+    pub colour: bool,
+}
+
+// a marker under the source, the primary one being where the error happened
+#[derive(Clone)]
+struct Marker {
+    location: Location,
+    width: usize,
+    text: String,
+    primary: bool,
+}
+
+impl<'a> Report<'a> {
+    pub fn new(source: &'a str) -> Report<'a> {
+        Report {
+            source,
+            file: None,
+            colour: false,
+        }
+    }
+
+    pub fn render<Tokens>(&self, err: &ParseError<Location, Tokens>) -> String where
+        Tokens: Display
+    {
+        self.render_as(Severity::Error, err)
+    }
+
+    // a bundle renders each of its errors in turn, however deeply it is nested
+    pub fn render_as<Tokens>(&self, severity: Severity, err: &ParseError<Location, Tokens>) -> String where
+        Tokens: Display
+    {
+        let mut reports = vec![];
+        collect_reports(err, &mut vec![], &mut reports);
+        let reports: Vec<String> = reports.into_iter()
+            .map(|(message, markers)| self.render_markers(severity, &message, markers))
+            .collect();
+        reports.join("\n")
+    }
+
+    fn paint(&self, colour: &str, text: &str) -> String {
+        if self.colour && !text.is_empty() {
+            format!("{}{}{}", colour, text, RESET)
+        } else {
+            text.into()
+        }
+    }
+
+    fn render_markers(&self, severity: Severity, message: &str, mut markers: Vec<Marker>) -> String {
+        // the primary marker then the innermost context first within a line
+        markers.reverse();
+        markers.sort_by_key(|marker| marker.location.line);
+        let primary = markers.iter()
+            .find(|marker| marker.primary)
+            .map_or(Location::default(), |marker| marker.location);
+        let gutter = markers.iter()
+            .map(|marker| marker.location.line.to_string().len())
+            .max()
+            .unwrap_or(1);
+        let pad = " ".repeat(gutter);
+        let bar = self.paint(BLUE, "|");
+
+        let mut out = format!(
+            "{}{}\n",
+            self.paint(severity.colour(), &format!("{}:", severity)),
+            self.paint(BOLD, &format!(" {}", message))
+        );
+        out += &format!(
+            "{}{} {}{}\n",
+            pad,
+            self.paint(BLUE, "-->"),
+            self.file.map_or(String::new(), |file| format!("{}:", file)),
+            primary
+        );
+        out += &format!("{} {}\n", pad, bar);
+        let mut line = None;
+        for marker in markers {
+            let text = self.source_line(marker.location.line);
+            if line != Some(marker.location.line) {
+                line = Some(marker.location.line);
+                out += &format!(
+                    "{} {} {}\n",
+                    self.paint(BLUE, &format!("{:>1$}", marker.location.line, gutter)),
+                    bar,
+                    text
+                );
+            }
+            // copies tabs so the marker lines up however they are displayed
+            let indent: String = text.chars()
+                .take(marker.location.column.saturating_sub(1))
+                .map(|c| if c == '\t' { '\t' } else { ' ' })
+                .collect();
+            let available = text.chars().count().saturating_sub(marker.location.column.saturating_sub(1));
+            let width = marker.width.min(available).max(1);
+            let (symbol, colour) = match marker.primary {
+                true => ("^", severity.colour()),
+                false => ("-", BLUE),
+            };
+            let underline = symbol.repeat(width);
+            let label = match marker.text.is_empty() {
+                true => String::new(),
+                false => format!(" {}", marker.text),
+            };
+            out += &format!("{} {} {}{}\n", pad, bar, indent, self.paint(colour, &format!("{}{}", underline, label)));
+        }
+        out
+    }
+
+    fn source_line(&self, line: usize) -> &'a str {
+        self.source.split('\n')
+            .nth(line.saturating_sub(1))
+            .map_or("", |text| text.strip_suffix('\r').unwrap_or(text))
+    }
+}
+
+// splits bundles into separate reports, each keeping the context markers of the errors above it
+fn collect_reports<Tokens>(err: &ParseError<Location, Tokens>, context: &mut Vec<Marker>, reports: &mut Vec<(String, Vec<Marker>)>) where
+    Tokens: Display
+{
+    match err {
+        ParseError::ContextualisedError(err, location) => {
+            context.push(Marker {
+                location: *location,
+                width: 1,
+                text: format!("in {}", err.0),
+                primary: false,
+            });
+            collect_reports(&err.1, context, reports);
+            context.pop();
+        },
+        ParseError::Bundle(errors) => {
+            for err in errors {
+                collect_reports(err, context, reports)
+            }
+        },
+        err => {
+            let mut markers = context.clone();
+            collect_markers(err, &mut markers);
+            reports.push((err.message(), markers))
+        },
+    }
+}
+
+fn collect_markers<Tokens>(err: &ParseError<Location, Tokens>, markers: &mut Vec<Marker>) where
+    Tokens: Display
+{
+    match err {
+        ParseError::BranchingError(_, found, location) | ParseError::ExpectedFound(_, found, location) => {
+            markers.push(Marker {
+                location: *location,
+                width: found.to_string().chars().count(),
+                text: err.expectation().unwrap_or_default(),
+                primary: true,
+            })
+        },
+        ParseError::Invalid(_, found, location) => {
+            markers.push(Marker {
+                location: *location,
+                width: found.to_string().chars().count(),
+                text: String::new(),
+                primary: true,
+            })
+        },
+        ParseError::Suggestion(err, _) => collect_markers(err, markers),
+        // split up by `collect_reports` before markers are collected
+        ParseError::ContextualisedError(..) | ParseError::Bundle(_) => (),
+    }
+}
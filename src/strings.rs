@@ -0,0 +1,234 @@
+use std::marker::PhantomData;
+use super::*;
+use super::text::{LexError, Scan};
+use errors::{FromInvalid, ParseError};
+use input::{FailureSink, Positioned};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StringErrorKind {
+    ExpectedQuote,
+    Unterminated,
+    UnknownEscape(char),
+    InvalidEscape,
+}
+
+// `offset` counts tokens from the start of the literal, pointing at the escape for escape errors
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct StringError {
+    pub kind: StringErrorKind,
+    pub offset: usize,
+}
+
+impl StringError {
+    pub fn new(kind: StringErrorKind, offset: usize) -> StringError {
+        StringError {
+            kind,
+            offset,
+        }
+    }
+}
+
+impl std::fmt::Display for StringError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            StringErrorKind::ExpectedQuote => write!(f, "expected a string literal"),
+            StringErrorKind::Unterminated => write!(f, "unterminated string literal"),
+            StringErrorKind::UnknownEscape(c) => write!(f, "unknown escape `{}` at offset {}", c.escape_default(), self.offset),
+            StringErrorKind::InvalidEscape => write!(f, "invalid escape at offset {}", self.offset),
+        }
+    }
+}
+
+impl std::error::Error for StringError {}
+
+impl<I, Tokens> FromInvalid<StringError, I> for ParseError<I::Pos, Tokens> where
+    I: Iterator + Clone + Positioned + FailureSink<ParseError<I::Pos, Tokens>>,
+    Tokens: FromIterator<I::Item>
+{
+    fn from_invalid(err: StringError, found: Vec<I::Item>, at: &I) -> ParseError<I::Pos, Tokens> {
+        let err = ParseError::invalid(err.to_string(), found.into_iter().collect(), at.pos());
+        at.record(&err);
+        err
+    }
+}
+
+// a bad escape is the sequence read from its escape char, an unterminated literal is the whole literal
+impl LexError for StringError {
+    fn span(&self, info: &ParseInfo) -> (usize, usize) {
+        match self.kind {
+            StringErrorKind::ExpectedQuote => (0, 1),
+            _ => (self.offset, info.read - self.offset),
+        }
+    }
+}
+
+impl From<StringError> for String {
+    fn from(err: StringError) -> String {
+        err.to_string()
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct StringFormat {
+    // a literal must close with the quote it opened with
+    pub quotes: Vec<char>,
+    // `None` disables escapes entirely
+    pub escape: Option<char>,
+    // `\xNN` for NN up to 7F
+    pub hex_escapes: bool,
+    // `\u{N}` with up to six hex digits
+    pub unicode_escapes: bool,
+    // `r"..."` and `r#"..."#`, never decoding escapes
+    pub raw: bool,
+}
+
+impl Default for StringFormat {
+    fn default() -> StringFormat {
+        StringFormat {
+            quotes: vec!['"'],
+            escape: Some('\\'),
+            hex_escapes: true,
+            unicode_escapes: true,
+            raw: false,
+        }
+    }
+}
+
+// Lexing
+
+impl<I> Scan<I> where
+    I: Iterator<Item=char> + Clone
+{
+    fn fence(&mut self, hashes: usize) -> bool {
+        (0..hashes).all(|_| self.eat(|c| *c == '#').is_some())
+    }
+
+    fn hex(&mut self, max_digits: usize) -> Option<u32> {
+        let mut val = None;
+        for _ in 0..max_digits {
+            match self.eat(char::is_ascii_hexdigit) {
+                Some(c) => val = Some(val.unwrap_or(0) * 16 + c.to_digit(16).unwrap()),
+                None => break,
+            }
+        }
+        val
+    }
+
+    fn escape(&mut self, format: &StringFormat, quote: char) -> Result<(), StringErrorKind> {
+        let c = self.eat(|_| true)
+            .ok_or(StringErrorKind::Unterminated)?;
+        let decoded = match c {
+            'n' => '\n',
+            't' => '\t',
+            'r' => '\r',
+            '0' => '\0',
+            'x' if format.hex_escapes => {
+                let digits = [self.eat(char::is_ascii_hexdigit), self.eat(char::is_ascii_hexdigit)];
+                match digits.map(|digit| digit.and_then(|c| c.to_digit(16))) {
+                    [Some(high), Some(low)] if high < 8 => char::from_u32(high * 16 + low).unwrap(),
+                    _ => return Err(StringErrorKind::InvalidEscape),
+                }
+            },
+            'u' if format.unicode_escapes => {
+                self.eat(|c| *c == '{')
+                    .ok_or(StringErrorKind::InvalidEscape)?;
+                let val = self.hex(6)
+                    .ok_or(StringErrorKind::InvalidEscape)?;
+                self.eat(|c| *c == '}')
+                    .ok_or(StringErrorKind::InvalidEscape)?;
+                char::from_u32(val)
+                    .ok_or(StringErrorKind::InvalidEscape)?
+            },
+            c if c == quote || Some(c) == format.escape => c,
+            c => return Err(StringErrorKind::UnknownEscape(c)),
+        };
+        self.text.push(decoded);
+        Ok(())
+    }
+
+    fn string(&mut self, format: &StringFormat) -> Result<String, StringError> {
+        let mut raw = None;
+        if format.raw {
+            self.attempt(|scan| {
+                scan.eat(|c| *c == 'r').is_some() && {
+                    let mut hashes = 0;
+                    while scan.eat(|c| *c == '#').is_some() {
+                        hashes += 1
+                    }
+                    raw = scan.eat(|c| format.quotes.contains(c))
+                        .map(|quote| (quote, hashes));
+                    raw.is_some()
+                }
+            });
+        }
+        let (quote, hashes) = match raw {
+            Some((quote, hashes)) => (quote, Some(hashes)),
+            None => (
+                self.eat(|c| format.quotes.contains(c))
+                    .ok_or(StringError::new(StringErrorKind::ExpectedQuote, 0))?,
+                None
+            ),
+        };
+        loop {
+            let offset = self.info.taken;
+            let mut res = Ok(());
+            // an escape is only kept once it decodes, leaving the input at the escape otherwise
+            if hashes.is_none() && self.attempt(|scan| {
+                scan.eat(|c| Some(*c) == format.escape).is_some() && {
+                    res = scan.escape(format, quote);
+                    res.is_ok()
+                }
+            }) {
+                continue
+            }
+            res.map_err(|kind| StringError::new(kind, offset))?;
+            match self.eat(|_| true) {
+                None => break Err(StringError::new(StringErrorKind::Unterminated, 0)),
+                Some(c) if c == quote => match hashes {
+                    Some(hashes) if !self.attempt(|scan| scan.fence(hashes)) => self.text.push(c),
+                    _ => break Ok(std::mem::take(&mut self.text)),
+                },
+                Some(c) => self.text.push(c),
+            }
+        }
+    }
+}
+
+// Parsers
+
+#[derive(Clone)]
+pub struct StringLiteral<E> {
+    format: StringFormat,
+    _e: PhantomData<E>,
+}
+
+impl<E> StringLiteral<E> {
+    pub fn new(format: StringFormat) -> StringLiteral<E> {
+        StringLiteral {
+            format,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, E> Parser<I> for StringLiteral<E> where
+    I: Iterator<Item=char> + Clone,
+    E: FromInvalid<StringError, I>
+{
+    type Value = String;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<String, E> {
+        let mut scan = Scan::new(iter.clone());
+        let res = scan.string(&self.format);
+        scan.finish(iter, res)
+    }
+}
+
+pub fn string_literal<E>() -> StringLiteral<E> {
+    StringLiteral::new(StringFormat::default())
+}
+
+pub fn string_literal_with<E>(format: StringFormat) -> StringLiteral<E> {
+    StringLiteral::new(format)
+}
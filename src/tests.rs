@@ -63,7 +63,7 @@ impl<I> Parser<I> for ExpectEnd where
     type Error = String;
 
     fn parse(&self, iter: &mut I) -> ParseResult<(), String> {
-        if let None = iter.next() {
+        if iter.next().is_none() {
             ParseInfo::new(0, 1).ok(())
         } else {
             ParseInfo::new(1, 1).err(self.err.clone())
@@ -111,7 +111,7 @@ fn test_wrap() {
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        wrap::<_, !>("abc".into())
+        wrap::<_, !>("abc")
         .parse(&mut iter).record_to(&mut info),
         Ok("abc")
     );
@@ -138,7 +138,7 @@ fn test_fail() {
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        fail::<!, _>(format!("err"))
+        fail::<!, _>("err".to_string())
         .parse(&mut iter).record_to(&mut info),
         Err("err".into())
     );
@@ -160,15 +160,14 @@ fn test_fail() {
 }
 
 #[test]
-fn test_discard() {
-    let mut iter = "abc".chars();
+fn test_tag() {
+    let mut iter = [1, 2, 3, 1, 2, 4].into_iter();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .discard()
+        tag::<_, String>([1, 2, 3])
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Ok(vec![1, 2, 3])
     );
     assert_eq!(
         info,
@@ -177,60 +176,29 @@ fn test_discard() {
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        tag::<_, String>([1, 2, 3])
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Err("expected `123`, found `4`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 1)
-    )
-}
-
-#[test]
-fn test_lense() {
-    struct WrappedIter<I: Iterator>(I);
-
-    impl<I: Iterator> WrappedIter<I> {
-        fn get_mut(&mut self) -> &mut I {
-            let WrappedIter(iter) = self;
-            iter
-        }
-    }
-
-    impl<I: Iterator> Iterator for WrappedIter<I> {
-        type Item = I::Item;
-
-        fn next(&mut self) -> Option<Self::Item> {
-            let WrappedIter(iter) = self;
-            iter.next()
-        }
-    }
-
-    impl<I: Iterator + Clone> Clone for WrappedIter<I> {
-        fn clone(&self) -> Self {
-            let WrappedIter(iter) = self;
-            WrappedIter(iter.clone())
-        }
-    }
-
-    let mut iter = WrappedIter("abc".chars());
+        ParseInfo::new(2, 3)
+    );
 
-    let mut info = ParseInfo::default();
+    info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .lense(WrappedIter::get_mut)
+        tag::<_, String>([4, 5])
         .parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        Err("expected `45`, found end of input".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(1, 2)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
         Ok(())
     );
@@ -240,41 +208,46 @@ fn test_lense() {
     )
 }
 
-// Backtracking
-
-// backtrack on failure
 #[test]
-fn test_attempt() {
-    let mut iter = "abc".chars();
+fn test_literal() {
+    let mut iter = "abcabd".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("def", "err")
-        .attempt()
+        literal::<String>("abc")
         .parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        Ok("abc".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 3)
+        ParseInfo::new(3, 3)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .attempt()
+        literal::<String>("abc")
         .parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        Err("expected `abc`, found `d`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(2, 3)
     );
 
+    info = ParseInfo::default();
+    assert_eq!(
+        literal::<String>("d")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("d".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 1)
+    );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
         Ok(())
     );
@@ -285,77 +258,78 @@ fn test_attempt() {
 }
 
 #[test]
-fn test_attempt_parse() {
-    let mut iter = "abc".chars();
+fn test_token() {
+    let mut iter = "ab".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("def", "err")
-        .attempt_parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        token::<_, String>('b')
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected `b`, found `a`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 3)
+        ParseInfo::new(0, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .attempt_parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        apply!(
+            |a, b| (a, b),
+            token::<_, String>('a'),
+            token::<_, String>('b')
+        )
+        .parse(&mut iter).record_to(&mut info),
+        Ok(('a', 'b'))
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(2, 2)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        token::<_, String>('c')
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Err("expected `c`, found end of input".into())
     );
     assert_eq!(
         info,
         ParseInfo::new(0, 1)
-    )
-}
-
-// backtrack on success
-#[test]
-fn test_scry() {
-    let mut iter = "abc".chars();
+    );
 
-    let mut info = ParseInfo::default();
+    info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .scry()
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        Ok(())
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 3)
-    );
+        ParseInfo::new(0, 1)
+    )
+}
 
-    info = ParseInfo::default();
+#[test]
+fn test_any_token() {
+    let mut iter = "a".chars();
+
+    let mut info = ParseInfo::default();
     assert_eq!(
-        expect("def", "err")
-        .scry()
+        any_token::<String>()
         .parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        Ok('a')
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        any_token::<String>()
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Err("unexpected end of input".into())
     );
     assert_eq!(
         info,
@@ -364,86 +338,76 @@ fn test_scry() {
 }
 
 #[test]
-fn test_scry_parse() {
-    let mut iter = "abc".chars();
+fn test_end_of_input() {
+    let mut iter = "a".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .scry_parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
-    );
-    assert_eq!(
-        info,
-        ParseInfo::new(0, 3)
-    );
-
-    info = ParseInfo::default();
-    assert_eq!(
-        expect("def", "err")
-        .scry_parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        end_of_input::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("unexpected `a`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(0, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        apply!(
+            |a, _| a,
+            any_token::<String>(),
+            end_of_input::<String>()
+        )
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Ok('a')
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 1)
+        ParseInfo::new(1, 2)
     )
 }
 
-// always backtrack
 #[test]
-fn test_backtrack() {
-    let mut iter = "abc".chars();
+fn test_satisfy() {
+    let mut iter = "a1".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .backtrack()
+        satisfy::<_, String>(char::is_ascii_alphabetic)
         .parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        Ok('a')
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 3)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("def", "err")
-        .backtrack()
+        satisfy::<_, String>(char::is_ascii_alphabetic)
         .parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        Err("unexpected `1`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 3)
+        ParseInfo::new(0, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
+        satisfy::<_, String>(char::is_ascii_digit)
         .parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        Ok('1')
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
         Ok(())
     );
@@ -454,47 +418,47 @@ fn test_backtrack() {
 }
 
 #[test]
-fn test_backtrack_parse() {
-    let mut iter = "abc".chars();
+fn test_one_of() {
+    let mut iter = "+*".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .backtrack_parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        one_of::<_, String>("+-".chars())
+        .parse(&mut iter).record_to(&mut info),
+        Ok('+')
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 3)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("def", "err")
-        .backtrack_parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        one_of::<_, String>("+-".chars())
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected one of `+`, `-`, found `*`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 3)
+        ParseInfo::new(0, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
+        any_token::<String>()
         .parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        Ok('*')
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        one_of::<_, String>("+-".chars())
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Err("expected one of `+`, `-`, found end of input".into())
     );
     assert_eq!(
         info,
@@ -502,57 +466,48 @@ fn test_backtrack_parse() {
     )
 }
 
-// Value mapping
-
 #[test]
-fn test_map() {
-    let mut iter = "abc".chars();
+fn test_none_of() {
+    let mut iter = "a\"".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .map(|s| s.to_uppercase())
+        none_of::<_, String>(['"'])
         .parse(&mut iter).record_to(&mut info),
-        Ok(format!("ABC"))
+        Ok('a')
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        none_of::<_, String>(['"'])
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Err("unexpected `\"`".into())
     );
     assert_eq!(
         info,
         ParseInfo::new(0, 1)
-    )
-}
-
-#[test]
-fn test_and_then() {
-    let mut iter = "abc".chars();
+    );
 
-    let mut info = ParseInfo::default();
+    info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .and_then(|s| Ok(s.to_uppercase()))
+        any_token::<String>()
         .parse(&mut iter).record_to(&mut info),
-        Ok("ABC".into())
+        Ok('"')
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        none_of::<_, String>(['"'])
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Err("unexpected end of input".into())
     );
     assert_eq!(
         info,
@@ -561,52 +516,45 @@ fn test_and_then() {
 }
 
 #[test]
-fn test_and_compose() {
-    let mut iter = "abcdef".chars();
+fn test_take_while() {
+    let mut iter = "123abc".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure_0")
-        .and_compose(expect("def", "test_failure_1"))
+        take_while::<_, !>(char::is_ascii_digit)
         .parse(&mut iter).record_to(&mut info),
-        Ok("def".into())
+        Ok(vec!['1', '2', '3'])
     );
     assert_eq!(
         info,
-        ParseInfo::new(6, 6)
+        ParseInfo::new(3, 4)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        take_while::<_, !>(char::is_ascii_digit)
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Ok(vec![])
     );
     assert_eq!(
         info,
         ParseInfo::new(0, 1)
-    )
-}
-
-#[test]
-fn test_preserve_and_compose() {
-    let mut iter = "abcdef".chars();
+    );
 
-    let mut info = ParseInfo::default();
+    info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure_0")
-        .preserve_and_compose(expect("def", "test_failure_1"))
+        take_while::<_, !>(char::is_ascii_alphabetic)
         .parse(&mut iter).record_to(&mut info),
-        Ok("abc".into())
+        Ok(vec!['a', 'b', 'c'])
     );
     assert_eq!(
         info,
-        ParseInfo::new(6, 6)
+        ParseInfo::new(3, 4)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
         Ok(())
     );
@@ -617,56 +565,45 @@ fn test_preserve_and_compose() {
 }
 
 #[test]
-fn test_and_then_compose() {
-    let mut iter = "abcABC".chars();
+fn test_take_while1() {
+    let mut iter = "abc123".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure_0")
-        .and_then_compose(
-            |s| expect(s.to_uppercase(), "test_failure_1")
-        )
+        take_while1::<_, String>(char::is_ascii_digit)
         .parse(&mut iter).record_to(&mut info),
-        Ok("ABC".into())
+        Err("unexpected `a`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(6, 6)
+        ParseInfo::new(0, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        take_while1::<_, String>(char::is_ascii_alphabetic)
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Ok(vec!['a', 'b', 'c'])
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 1)
-    )
-}
-
-// Error mapping
-
-#[test]
-fn test_map_err() {
-    let mut iter = "def".chars();
+        ParseInfo::new(3, 4)
+    );
 
-    let mut info = ParseInfo::default();
+    info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "err")
-        .map_err(|e| e.to_uppercase())
+        take_while1::<_, String>(char::is_ascii_digit)
         .parse(&mut iter).record_to(&mut info),
-        Err("ERR".into())
+        Ok(vec!['1', '2', '3'])
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(3, 4)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
         Ok(())
     );
@@ -677,15 +614,14 @@ fn test_map_err() {
 }
 
 #[test]
-fn test_or_else() {
-    let mut iter = "def".chars();
+fn test_take_while_m_n() {
+    let mut iter = "12345a".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "err")
-        .or_else(|e| Err(e.to_uppercase()))
+        take_while_m_n::<_, String>(2, 3, char::is_ascii_digit)
         .parse(&mut iter).record_to(&mut info),
-        Err("ERR".into())
+        Ok(vec!['1', '2', '3'])
     );
     assert_eq!(
         info,
@@ -694,35 +630,29 @@ fn test_or_else() {
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        take_while_m_n::<_, String>(3, 4, char::is_ascii_digit)
         .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        Err("unexpected `a`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(0, 1)
-    )
-}
-
-#[test]
-fn test_or_compose() {
-    let mut iter = "defghi".chars();
+        ParseInfo::new(2, 3)
+    );
 
-    let mut info = ParseInfo::default();
+    info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "err")
-        .or_compose(expect("ghi", "test_failure"))
+        token::<_, String>('a')
         .parse(&mut iter).record_to(&mut info),
-        Ok("ghi".into())
+        Ok('a')
     );
     assert_eq!(
         info,
-        ParseInfo::new(6, 6)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
         Ok(())
     );
@@ -733,78 +663,61 @@ fn test_or_compose() {
 }
 
 #[test]
-fn test_or_else_compose() {
-    let mut iter = "defghijkl".chars();
+fn test_take_while_m_n_max_below_min() {
+    let mut iter = "12345".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "err")
-        .or_else_compose(|e| expect("def", e))
+        take_while_m_n::<_, String>(3, 2, char::is_ascii_digit)
         .parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        Err("unexpected `3`".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(6, 6)
-    );
+        ParseInfo::new(2, 2)
+    )
+}
 
-    info = ParseInfo::default();
+#[test]
+fn test_take_until() {
+    let mut iter = "ab*/cd".chars();
+
+    let mut info = ParseInfo::default();
     assert_eq!(
-        expect("jkl", "test_failure")
-        .or_else_compose(|e| expect("mno", e))
+        take_until(literal::<String>("*/"))
         .parse(&mut iter).record_to(&mut info),
-        Ok("jkl".into())
+        Ok(vec!['a', 'b'])
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(2, 4)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
-        .parse(&mut iter).record_to(&mut info),
-        Ok(())
-    );
-    assert_eq!(
-        info,
-        ParseInfo::new(0, 1)
-    )
-}
-
-// Vector Combinators
-
-#[test]
-fn test_many() {
-    let mut iter = "abcabcdefghi".chars();
-
-    let mut info = ParseInfo::default();
-    assert_eq!(
-        expect("abc", "test_failure")
-        .many()
-        .map_err::<!, _>(|_: !| unreachable!())
+        literal::<String>("*/")
         .parse(&mut iter).record_to(&mut info),
-        Ok(vec!["abc".into(), "abc".into()])
+        Ok("*/".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(9, 9)
+        ParseInfo::new(2, 2)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("ghi", "test_failure")
+        take_until(literal::<String>("*/"))
         .parse(&mut iter).record_to(&mut info),
-        Ok("ghi".into())
+        Err("expected `*/`, found end of input".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(2, 3)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
         Ok(())
     );
@@ -815,47 +728,45 @@ fn test_many() {
 }
 
 #[test]
-fn test_some() {
-    let mut iter = "defghighijklmno".chars();
+fn test_skip_until() {
+    let mut iter = "ab;cd".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "err")
-        .some()
+        skip_until::<_, String>([';', '}'])
         .parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        Ok(())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(2, 3)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("ghi", "test_failure")
-        .some()
+        token::<_, String>(';')
         .parse(&mut iter).record_to(&mut info),
-        Ok(vec!["ghi".into(), "ghi".into()])
+        Ok(';')
     );
     assert_eq!(
         info,
-        ParseInfo::new(9, 9)
+        ParseInfo::new(1, 1)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("mno", "test_failure")
+        skip_until::<_, String>([';', '}'])
         .parse(&mut iter).record_to(&mut info),
-        Ok("mno".into())
+        Ok(())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(2, 3)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
+        end_of_input::<String>()
         .parse(&mut iter).record_to(&mut info),
         Ok(())
     );
@@ -866,38 +777,66 @@ fn test_some() {
 }
 
 #[test]
-fn test_least_until() {
-    let mut iter = ".def.def.def:ghi".chars();
+fn test_skip_balanced() {
+    let mut iter = "f(a; [b)]) ; x}".chars();
 
+    // nested separators and mismatched closing tokens are skipped over
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure")
-        .least_until(expect(":", "err"))
+        skip_balanced::<_, String>([';'], text::BRACKETS)
         .parse(&mut iter).record_to(&mut info),
-        Err("err".into())
+        Ok(())
     );
     assert_eq!(
         info,
-        ParseInfo::new(4, 4)
+        ParseInfo::new(11, 12)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("def", "test_failure_0")
-        .least_until(expect(":", "test_failure_1"))
+        token::<_, String>(';')
         .parse(&mut iter).record_to(&mut info),
-        Ok((vec!["def".into(), "def".into()], ":".into()))
+        Ok(';')
     );
     assert_eq!(
         info,
-        ParseInfo::new(9, 9)
+        ParseInfo::new(1, 1)
     );
 
+    // stops before the end of the group it started in
     info = ParseInfo::default();
     assert_eq!(
-        expect("ghi", "test_failure")
+        skip_balanced::<_, String>([';'], text::BRACKETS)
         .parse(&mut iter).record_to(&mut info),
-        Ok("ghi".into())
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        token::<_, String>('}')
+        .parse(&mut iter).record_to(&mut info),
+        Ok('}')
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 1)
+    )
+}
+
+#[test]
+fn test_discard() {
+    let mut iter = "abc".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure")
+        .discard()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
     );
     assert_eq!(
         info,
@@ -917,19 +856,44 @@ fn test_least_until() {
 }
 
 #[test]
-fn test_most_until() {
-    let mut iter = "abcabcdef".chars();
+fn test_lense() {
+    struct WrappedIter<I: Iterator>(I);
+
+    impl<I: Iterator> WrappedIter<I> {
+        fn get_mut(&mut self) -> &mut I {
+            let WrappedIter(iter) = self;
+            iter
+        }
+    }
+
+    impl<I: Iterator> Iterator for WrappedIter<I> {
+        type Item = I::Item;
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let WrappedIter(iter) = self;
+            iter.next()
+        }
+    }
+
+    impl<I: Iterator + Clone> Clone for WrappedIter<I> {
+        fn clone(&self) -> Self {
+            let WrappedIter(iter) = self;
+            WrappedIter(iter.clone())
+        }
+    }
+
+    let mut iter = WrappedIter("abc".chars());
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure_0")
-        .most_until(expect("def", "test_failure_1"))
+        expect("abc", "test_failure")
+        .lense(WrappedIter::get_mut)
         .parse(&mut iter).record_to(&mut info),
-        Ok((vec!["abc".into(), "abc".into()], "def".into()))
+        Ok("abc".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(9, 9)
+        ParseInfo::new(3, 3)
     );
 
     info = ParseInfo::default();
@@ -944,36 +908,38 @@ fn test_most_until() {
     )
 }
 
-// Error recovery
+// Backtracking
 
+// backtrack on failure
 #[test]
-fn test_continue_with() {
-    let mut iter = "abcdefghijkl".chars();
+fn test_attempt() {
+    let mut iter = "abc".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure_0")
-        .continue_with(expect("def", "test_failure_1").discard())
+        expect("def", "err")
+        .attempt()
         .parse(&mut iter).record_to(&mut info),
-        Ok(Ok("abc".into()))
+        Err("err".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(6, 6)
+        ParseInfo::new(0, 3)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("def", "err")
-        .continue_with(expect("jkl", "test_failure").discard())
+        expect("abc", "test_failure")
+        .attempt()
         .parse(&mut iter).record_to(&mut info),
-        Ok(Err("err".into()))
+        Ok("abc".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(6, 6)
+        ParseInfo::new(3, 3)
     );
 
+
     info = ParseInfo::default();
     assert_eq!(
         expect_end("test_failure")
@@ -987,31 +953,29 @@ fn test_continue_with() {
 }
 
 #[test]
-fn test_recover_with() {
-    let mut iter = "abcdefghi".chars();
+fn test_attempt_parse() {
+    let mut iter = "abc".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "test_failure_0")
-        .recover_with(expect("def", "test_failure_1").discard())
-        .parse(&mut iter).record_to(&mut info),
-        Ok(Ok("abc".into()))
+        expect("def", "err")
+        .attempt_parse(&mut iter).record_to(&mut info),
+        Err("err".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(0, 3)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("abc", "err")
-        .recover_with(expect("ghi", "test_failure").discard())
-        .parse(&mut iter).record_to(&mut info),
-        Ok(Err("err".into()))
+        expect("abc", "test_failure")
+        .attempt_parse(&mut iter).record_to(&mut info),
+        Ok("abc".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(6, 6)
+        ParseInfo::new(3, 3)
     );
 
     info = ParseInfo::default();
@@ -1026,28 +990,27 @@ fn test_recover_with() {
     )
 }
 
+// backtrack on success
 #[test]
-fn test_absorb_err() {
-    let mut iter = "abcdef".chars();
-    
+fn test_scry() {
+    let mut iter = "abc".chars();
+
     let mut info = ParseInfo::default();
     assert_eq!(
         expect("abc", "test_failure")
-        .map(Result::Ok)
-        .absorb_err()
+        .scry()
         .parse(&mut iter).record_to(&mut info),
         Ok("abc".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(0, 3)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect("def", "test_failure")
-        .map(|_| Err("err".into()))
-        .absorb_err::<!>()
+        expect("def", "err")
+        .scry()
         .parse(&mut iter).record_to(&mut info),
         Err("err".into())
     );
@@ -1068,78 +1031,131 @@ fn test_absorb_err() {
     )
 }
 
-// Functionality Testing
-
-#[derive(Debug, PartialEq, Eq)]
-enum TestRecExpr {
-    Abc,
-    Exprs(Vec<TestRecExpr>)
-}
-
 #[test]
-fn test_recursive_capability() {
-    use TestRecExpr::*;
-    let expr_parser: ForwardDef<'_, std::str::Chars<'static>, TestRecExpr, String> = ForwardDef::new();
-    let inner_expr_parser =
-        expect("abc", "expected 'abc'").map(|_| Abc)
-        .attempt()
-        .or_compose(
-            expect("(", "expected '('")
-            .and_compose(
-                expr_parser.reference()
-                .most_until(
-                    expect(")", "expected ')'")
-                    .attempt()
-                )
-                .map(|(exprs, _)| Exprs(exprs))
-            )
-        );
-    let Ok(()) = expr_parser.define(&inner_expr_parser) else {
-        unreachable!();
-    };
+fn test_scry_parse() {
     let mut iter = "abc".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expr_parser
-        .parse(&mut iter).record_to(&mut info),
-        Ok(
-            Abc
-        )
+        expect("abc", "test_failure")
+        .scry_parse(&mut iter).record_to(&mut info),
+        Ok("abc".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(3, 3)
+        ParseInfo::new(0, 3)
     );
 
     info = ParseInfo::default();
     assert_eq!(
-        expect_end("test_failure")
-        .parse(&mut iter).record_to(&mut info),
-        Ok(())
+        expect("def", "err")
+        .scry_parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
     );
     assert_eq!(
         info,
         ParseInfo::new(0, 1)
+    )
+}
+
+// always backtrack
+#[test]
+fn test_backtrack() {
+    let mut iter = "abc".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure")
+        .backtrack()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("abc".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 3)
     );
 
-    let mut iter = "(abcabcabc)".chars();
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("def", "err")
+        .backtrack()
+        .parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("abc".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_backtrack_parse() {
+    let mut iter = "abc".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expr_parser
+        expect("abc", "test_failure")
+        .backtrack_parse(&mut iter).record_to(&mut info),
+        Ok("abc".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("def", "err")
+        .backtrack_parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure")
         .parse(&mut iter).record_to(&mut info),
-        Ok(
-            Exprs(vec![
-                Abc,
-                Abc,
-                Abc
-            ])
-        )
+        Ok("abc".into())
     );
     assert_eq!(
         info,
-        ParseInfo::new(11, 13)
+        ParseInfo::new(3, 3)
     );
 
     info = ParseInfo::default();
@@ -1151,29 +1167,125 @@ fn test_recursive_capability() {
     assert_eq!(
         info,
         ParseInfo::new(0, 1)
+    )
+}
+
+// stop backtracking
+#[test]
+fn test_cut() {
+    let mut iter = "fnx".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("fn", "test_failure")
+        .cut()
+        .and_compose(expect("(", "expected '('"))
+        .attempt()
+        .or_compose(expect("fnx", "test_failure"))
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected '('".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3).commit()
     );
 
-    let mut iter = "(abcabc(abcabc)abc)".chars();
+    let mut iter = "fn(fnx".chars();
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("fn", "test_failure")
+        .cut()
+        .and_compose(expect("(", "expected '('"))
+        .many()
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected '('".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6).commit()
+    );
+
+    let mut iter = "fn".chars();
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("fn", "test_failure")
+        .cut()
+        .attempt()
+        .or_compose(expect("x", "test_failure"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("fn".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 2)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    // a cut within lookahead is undone with the input
+    let mut iter = "fnord".chars();
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("fn", "test_failure")
+        .cut()
+        .scry()
+        .and_compose(expect("x", "expected x"))
+        .attempt()
+        .or_compose(expect("fnord", "test_failure"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("fnord".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(5, 5)
+    );
+
+    let mut iter = "fnord".chars();
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("fn", "test_failure")
+        .cut()
+        .and_compose(expect("x", "expected x"))
+        .backtrack()
+        .or_compose(expect("fnord", "test_failure"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("fnord".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(5, 5)
+    )
+}
+
+// Value mapping
+
+#[test]
+fn test_map() {
+    let mut iter = "abc".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        expr_parser
+        expect("abc", "test_failure")
+        .map(|s| s.to_uppercase())
         .parse(&mut iter).record_to(&mut info),
-        Ok(
-            Exprs(vec![
-                Abc,
-                Abc,
-                Exprs(vec![
-                    Abc,
-                    Abc
-                ]),
-                Abc
-            ])
-        )
+        Ok("ABC".to_string())
     );
     assert_eq!(
         info,
-        ParseInfo::new(19, 21)
+        ParseInfo::new(3, 3)
     );
 
     info = ParseInfo::default();
@@ -1189,24 +1301,47 @@ fn test_recursive_capability() {
 }
 
 #[test]
-fn test_apply_macro() {
-    let mut iter = "abcdefghi".chars();
+fn test_and_then() {
+    let mut iter = "abc".chars();
 
     let mut info = ParseInfo::default();
     assert_eq!(
-        apply!(
-            |a, b, c| (a, b, c),
-            expect("abc", "test_failure_0"),
-            expect("def", "test_failure_1"),
-            expect("ghi", "test_failure_2")
+        expect("abc", "test_failure")
+        .and_then(|s| Ok(s.to_uppercase()))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("ABC".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
 
-        )
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
         .parse(&mut iter).record_to(&mut info),
-        Ok(("abc".into(), "def".into(), "ghi".into()))
+        Ok(())
     );
     assert_eq!(
         info,
-        ParseInfo::new(9, 9)
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_and_compose() {
+    let mut iter = "abcdef".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure_0")
+        .and_compose(expect("def", "test_failure_1"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("def".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
     );
 
     info = ParseInfo::default();
@@ -1219,4 +1354,2670 @@ fn test_apply_macro() {
         info,
         ParseInfo::new(0, 1)
     )
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_preserve_and_compose() {
+    let mut iter = "abcdef".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure_0")
+        .preserve_and_compose(expect("def", "test_failure_1"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("abc".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_and_then_compose() {
+    let mut iter = "abcABC".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure_0")
+        .and_then_compose(
+            |s| expect(s.to_uppercase(), "test_failure_1")
+        )
+        .parse(&mut iter).record_to(&mut info),
+        Ok("ABC".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+// Error mapping
+
+#[test]
+fn test_map_err() {
+    let mut iter = "def".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "err")
+        .map_err(|e| e.to_uppercase())
+        .parse(&mut iter).record_to(&mut info),
+        Err("ERR".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_or_else() {
+    let mut iter = "def".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "err")
+        .or_else(|e| Err(e.to_uppercase()))
+        .parse(&mut iter).record_to(&mut info),
+        Err("ERR".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_or_compose() {
+    let mut iter = "defghi".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "err")
+        .or_compose(expect("ghi", "test_failure"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("ghi".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_or_else_compose() {
+    let mut iter = "defghijkl".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "err")
+        .or_else_compose(|e| expect("def", e))
+        .parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("jkl", "test_failure")
+        .or_else_compose(|e| expect("mno", e))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("jkl".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+// Vector Combinators
+
+#[test]
+fn test_many() {
+    let mut iter = "abcabcdefghi".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure")
+        .many()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec!["abc".into(), "abc".into()])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(9, 9)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("ghi", "test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("ghi".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "`many` parsed an item without consuming any input")]
+fn test_many_zero_progress() {
+    let mut iter = "abc".chars();
+    let _ = Many::new(skip_while::<_, String>(|c: &char| c.is_whitespace()))
+        .parse(&mut iter);
+}
+
+#[test]
+#[cfg(debug_assertions)]
+#[should_panic(expected = "`some` parsed an item without consuming any input")]
+fn test_some_zero_progress() {
+    let mut iter = " abc".chars();
+    let _ = Some::new(skip_while::<_, String>(|c: &char| c.is_whitespace()))
+        .parse(&mut iter);
+}
+
+// release builds stop at the item that consumed nothing, keeping what came before
+#[test]
+#[cfg(not(debug_assertions))]
+fn test_zero_progress_release() {
+    let mut iter = " abc".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        Some::new(skip_while::<_, String>(|c: &char| c.is_whitespace()))
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec![()])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 2)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        Repeat::new(skip_while::<_, String>(|c: &char| c.is_whitespace()), 0, None)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec![])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+    assert_eq!(
+        iter.next(),
+        Option::Some('a')
+    )
+}
+
+#[test]
+fn test_some() {
+    let mut iter = "defghighijklmno".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "err")
+        .some()
+        .parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("ghi", "test_failure")
+        .some()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec!["ghi".into(), "ghi".into()])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(9, 9)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("mno", "test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("mno".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_repeat() {
+    let mut iter = "abababx".chars();
+
+    // stops at the maximum without reading further
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("ab", "test_failure")
+        .repeat(2)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec!["ab".into(), "ab".into()])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 4)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("ab", "test_failure")
+        .attempt()
+        .repeat(2..=3)
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected at least 2 items, found `x`".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 4)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("x", "test_failure")
+        .at_most(3)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec!["x".into()])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 2)
+    );
+
+    // an empty range matches no items
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("x", "test_failure")
+        .repeat(3..3)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec![])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 0)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_sep_by() {
+    let item = || satisfy::<_, String>(|c: &char| c.is_ascii_alphabetic());
+
+    // the trailing separator is left for whatever follows
+    let mut iter = "a,b,)".chars();
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        SepBy::new(item(), token(','), 0, Trailing::Forbidden)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec!['a', 'b'])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 5)
+    );
+    assert_eq!(
+        iter.as_str(),
+        ",)"
+    );
+
+    let mut iter = "a,b,)".chars();
+    info = ParseInfo::default();
+    assert_eq!(
+        SepBy::new(item(), token(','), 0, Trailing::Optional)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec!['a', 'b'])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 5)
+    );
+    assert_eq!(
+        iter.as_str(),
+        ")"
+    );
+
+    let mut iter = "a;b".chars();
+    info = ParseInfo::default();
+    assert_eq!(
+        SepBy::new(item(), token(';'), 0, Trailing::Required)
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected `;`, found end of input".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 4)
+    );
+
+    let mut iter = ")".chars();
+    info = ParseInfo::default();
+    assert_eq!(
+        SepBy::new(item(), token(','), 1, Trailing::Forbidden)
+        .parse(&mut iter).record_to(&mut info),
+        Err("unexpected `)`".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        SepBy::new(item(), token(','), 0, Trailing::Forbidden)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec![])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_least_until() {
+    let mut iter = ".def.def.def:ghi".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure")
+        .least_until(expect(":", "err"))
+        .parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 4)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("def", "test_failure_0")
+        .least_until(expect(":", "test_failure_1"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok((vec!["def".into(), "def".into()], ":".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(9, 9)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("ghi", "test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("ghi".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_most_until() {
+    let mut iter = "abcabcdef".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure_0")
+        .most_until(expect("def", "test_failure_1"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok((vec!["abc".into(), "abc".into()], "def".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(9, 9)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+// Error recovery
+
+#[test]
+fn test_continue_with() {
+    let mut iter = "abcdefghijkl".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure_0")
+        .continue_with(expect("def", "test_failure_1").discard())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(Ok("abc".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("def", "err")
+        .continue_with(expect("jkl", "test_failure").discard())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(Err("err".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_recover_with() {
+    let mut iter = "abcdefghi".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure_0")
+        .recover_with(expect("def", "test_failure_1").discard())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(Ok("abc".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "err")
+        .recover_with(expect("ghi", "test_failure").discard())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(Err("err".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_recover_until() {
+    let mut iter = "ab;x (;);ab".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("ab", "test_failure")
+        .recover_until([';'], "?".into())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(("ab".into(), None))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 2)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect(";", "test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(";".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 1)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("ab", "err")
+        .recover_balanced([';'], text::BRACKETS, "?".into())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(("?".into(), Some("err".into())))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(5, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect(";ab", "test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(";ab".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    )
+}
+
+#[test]
+fn test_recover_reported() {
+    use input::*;
+    use report::Severity;
+    let mut iter = Diagnostics::new("abcdefghi".chars());
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure_0")
+        .recover_reported(expect("def", "test_failure_1").discard())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(Some("abc".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "err")
+        .recover_reported(expect("ghi", "test_failure").discard())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(None)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+    assert_eq!(
+        iter.diagnostics(),
+        vec![(Severity::Error, "err".into())]
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_parse_with_diagnostics() {
+    use input::*;
+    use report::Severity;
+
+    // the warning is dropped along with the branch that reported it
+    let parser = expect("ab", "test_failure_0")
+        .diagnose(|_: &String| Some((Severity::Warning, "dropped".into())))
+        .and_compose(expect("x", "test_failure_1"))
+        .attempt()
+        .or_compose(expect("abc", "test_failure_2")
+            .diagnose(|val: &String| Some((Severity::Note, format!("read {}", val))))
+        )
+        .continue_reported(expect("d", "err").discard());
+    assert_eq!(
+        parse_with_diagnostics(&parser, "abcd".chars()),
+        (Ok(Some("abc".into())), vec![(Severity::Note, "read abc".into())])
+    );
+    assert_eq!(
+        parse_with_diagnostics(&parser, "abd".chars()),
+        (Err("err".into()), vec![])
+    );
+}
+
+#[test]
+fn test_repair() {
+    use errors::*;
+    use input::*;
+    use report::Severity;
+    type Error = ParseError<Location, String>;
+    let mut iter = Diagnostics::new(Repairable::new(Located::new(",);".chars())));
+
+    // the stray ',' is deleted
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        Repair::new(token::<_, Error>(')'), ')')
+        .parse(&mut iter).record_to(&mut info),
+        Ok(')')
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 2)
+    );
+
+    // the missing ')' is inserted
+    info = ParseInfo::default();
+    assert_eq!(
+        Repair::new(token::<_, Error>(')'), ')')
+        .parse(&mut iter).record_to(&mut info),
+        Ok(')')
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 2)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        token::<_, Error>(';')
+        .parse(&mut iter).record_to(&mut info),
+        Ok(';')
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 1)
+    );
+    assert_eq!(
+        iter.diagnostics(),
+        vec![
+            (Severity::Error, ParseError::branching(vec![], ",".into(), Location::default())),
+            (Severity::Error, ParseError::expected_found(")".into(), ";".into(), Location::new(2, 2, 1, 3))),
+        ]
+    );
+
+    // the missing ')' is inserted where the call failed, keeping "(x"
+    let mut iter = Diagnostics::new(Repairable::new(Located::new("(x;".chars())));
+    info = ParseInfo::default();
+    assert_eq!(
+        Repair::new(seq((token::<_, Error>('('), token('x'), token(')'))), ('(', 'x', ')'))
+        .parse(&mut iter).record_to(&mut info),
+        Ok(('(', 'x', ')'))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 4)
+    );
+    assert_eq!(
+        iter.next(),
+        Some(';')
+    );
+    assert_eq!(
+        iter.diagnostics(),
+        vec![(Severity::Error, ParseError::expected_found(")".into(), ";".into(), Location::new(2, 2, 1, 3)))]
+    );
+
+    // the stray ',' is deleted where the call failed
+    let mut iter = Diagnostics::new(Repairable::new(Located::new("(x,);".chars())));
+    info = ParseInfo::default();
+    assert_eq!(
+        Repair::new(seq((token::<_, Error>('('), token('x'), token(')'))), ('(', 'x', ')'))
+        .parse(&mut iter).record_to(&mut info),
+        Ok(('(', 'x', ')'))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 4)
+    );
+    assert_eq!(
+        iter.next(),
+        Some(';')
+    );
+    assert_eq!(
+        iter.diagnostics(),
+        vec![(Severity::Error, ParseError::branching(vec![], ",".into(), Location::new(2, 2, 1, 3)))]
+    );
+
+    // a recognized slice spans the deleted token, as it is cut from the source
+    let mut iter = Diagnostics::new(Repairable::new(StrInput::new("a,b;")));
+    info = ParseInfo::default();
+    assert_eq!(
+        Repair::new(RecognizeSlice::new(seq((token::<_, ParseError<usize, String>>('a'), token('b')))), "")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("a,b")
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+    assert_eq!(
+        (Positioned::pos(&iter), iter.as_slice()),
+        (3, ";")
+    );
+    assert_eq!(
+        iter.diagnostics(),
+        vec![(Severity::Error, ParseError::branching(vec![], ",".into(), 1))]
+    )
+}
+
+#[test]
+fn test_absorb_err() {
+    let mut iter = "abcdef".chars();
+    
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure")
+        .map(Result::Ok)
+        .absorb_err()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("abc".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("def", "test_failure")
+        .map(|_| Err("err".into()))
+        .absorb_err::<!>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+// Functionality Testing
+
+#[derive(Debug, PartialEq, Eq)]
+enum TestRecExpr {
+    Abc,
+    Exprs(Vec<TestRecExpr>)
+}
+
+#[test]
+fn test_recursive_capability() {
+    use TestRecExpr::*;
+    let expr_parser: ForwardDef<'_, std::str::Chars<'static>, TestRecExpr, String> = ForwardDef::new();
+    let inner_expr_parser =
+        expect("abc", "expected 'abc'").map(|_| Abc)
+        .attempt()
+        .or_compose(
+            expect("(", "expected '('")
+            .and_compose(
+                expr_parser.reference()
+                .most_until(
+                    expect(")", "expected ')'")
+                    .attempt()
+                )
+                .map(|(exprs, _)| Exprs(exprs))
+            )
+        );
+    let Ok(()) = expr_parser.define(&inner_expr_parser) else {
+        unreachable!();
+    };
+    let mut iter = "abc".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expr_parser
+        .parse(&mut iter).record_to(&mut info),
+        Ok(
+            Abc
+        )
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    let mut iter = "(abcabcabc)".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expr_parser
+        .parse(&mut iter).record_to(&mut info),
+        Ok(
+            Exprs(vec![
+                Abc,
+                Abc,
+                Abc
+            ])
+        )
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(11, 13)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    let mut iter = "(abcabc(abcabc)abc)".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expr_parser
+        .parse(&mut iter).record_to(&mut info),
+        Ok(
+            Exprs(vec![
+                Abc,
+                Abc,
+                Exprs(vec![
+                    Abc,
+                    Abc
+                ]),
+                Abc
+            ])
+        )
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(19, 21)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_apply_macro() {
+    let mut iter = "abcdefghi".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        apply!(
+            |a, b, c| (a, b, c),
+            expect("abc", "test_failure_0"),
+            expect("def", "test_failure_1"),
+            expect("ghi", "test_failure_2")
+
+        )
+        .parse(&mut iter).record_to(&mut info),
+        Ok(("abc".into(), "def".into(), "ghi".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(9, 9)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+#[test]
+fn test_seq() {
+    let mut iter = "abcdefghijklmno".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        seq((
+            expect("abc", "test_failure_0"),
+            expect("def", "test_failure_1"),
+            expect("ghi", "test_failure_2"),
+            expect("jkl", "test_failure_3"),
+            expect("mno", "test_failure_4")
+        ))
+        .parse(&mut iter).record_to(&mut info),
+        Ok(("abc".into(), "def".into(), "ghi".into(), "jkl".into(), "mno".into()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(15, 15)
+    );
+
+    let mut iter = "abcdefghi".chars();
+    info = ParseInfo::default();
+    assert_eq!(
+        seq((
+            expect("abc", "test_failure_0"),
+            expect("xyz", "err"),
+            expect("ghi", "test_failure_1")
+        ))
+        .parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    )
+}
+
+#[test]
+fn test_between() {
+    let mut iter = "(abc)def;".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        between(expect("(", "test_failure_0"), expect("abc", "test_failure_1"), expect(")", "test_failure_2"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("abc".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(5, 5)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        terminated(expect("def", "test_failure_0"), expect(";", "test_failure_1"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("def".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 4)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect_end("test_failure")
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+// Text
+
+#[test]
+fn test_char_classes() {
+    let mut iter = "7fé٣ \t".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        apply!(
+            |a, b, c, d| vec![a, b, c, d],
+            text::ascii_digit::<String>(),
+            text::hex_digit::<String>(),
+            text::alphabetic::<String>(),
+            text::digit::<String>()
+        )
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec!['7', 'f', 'é', '٣'])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 4)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::ascii_alphanumeric::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("unexpected ` `".into())
+    );
+    assert!(["½", "Ⅻ", "²", "٩", "𝟗", "9"].iter()
+        .map(|c| text::is_decimal_digit(&c.chars().next().unwrap()))
+        .eq([false, false, false, true, true, true]));
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::skip_whitespace::<!>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    )
+}
+
+#[test]
+fn test_digits() {
+    let mut iter = "2024ab".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        text::ascii_digits::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("2024".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 5)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::ascii_digits::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("unexpected `a`".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::alphanumerics::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("ab".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    )
+}
+
+#[test]
+fn test_newline() {
+    let mut iter = "\n\r\n\rx".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        text::newline::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("\n")
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 1)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::newline::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("\r\n")
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 2)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::newline::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("unexpected `\r`".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 2)
+    );
+
+    let mut iter = input::Located::new("\rx".chars());
+    info = ParseInfo::default();
+    assert_eq!(
+        text::newline::<errors::ParseError<input::Location, String>>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(errors::ParseError::branching(vec![], "\r".into(), input::Location::default()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 2)
+    )
+}
+
+#[test]
+fn test_identifier() {
+    let mut iter = "_größe2 9lives".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        text::identifier::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("_größe2".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(7, 8)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::whitespace::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(' ')
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 1)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::identifier::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("unexpected `9`".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_literal_no_case() {
+    let mut iter = "SeLeCt sELF".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        text::literal_no_case::<String>("select")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("SeLeCt".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 6)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        text::literal_no_case::<String>(" select")
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected ` select`, found `F`".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 5)
+    )
+}
+
+// Numbers
+
+#[test]
+fn test_integer() {
+    use numbers::*;
+    let mut iter = "-1_000 0xFF_ff 300 -1".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        integer_with::<i64, NumberError>(NumberFormat::RUST)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(-1000)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 7)
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        integer_with::<u64, NumberError>(NumberFormat::RUST)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(0xffff)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(7, 8)
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        integer::<u8, NumberError>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(NumberError::Overflow)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 4)
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        integer::<u8, String>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected digits".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    let mut iter = "12".chars();
+    info = ParseInfo::default();
+    assert_eq!(
+        integer_with::<u8, NumberError>(NumberFormat { radix: 40, ..NumberFormat::DECIMAL })
+        .parse(&mut iter).record_to(&mut info),
+        Err(NumberError::InvalidRadix)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 0)
+    )
+}
+
+#[test]
+fn test_integer_and_then() {
+    use numbers::*;
+    let mut iter = "0b1010 12".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        SizedParser::<std::str::Chars>::and_then(
+            integer_with::<u8, String>(NumberFormat::RUST),
+            |n| if n < 10 { Ok(n) } else { Err(format!("{n} is not a digit")) }
+        )
+        .parse(&mut iter).record_to(&mut info),
+        Err("10 is not a digit".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 7)
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        integer_with::<u8, String>(NumberFormat { radix: 16, ..NumberFormat::DECIMAL })
+        .parse(&mut iter).record_to(&mut info),
+        Ok(0x12)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    )
+}
+
+#[test]
+fn test_float() {
+    use numbers::*;
+    let mut iter = "1.5e-3 2.x 1e400 -INF".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        float::<f64, NumberError>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(1.5e-3)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 7)
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        float::<f64, NumberError>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(2.0)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 3)
+    );
+
+    iter.nth(2);
+    info = ParseInfo::default();
+    assert_eq!(
+        float::<f64, NumberError>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(NumberError::Overflow)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(5, 6)
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        float_with::<f32, NumberError>(NumberFormat { special: true, ..NumberFormat::DECIMAL })
+        .parse(&mut iter).record_to(&mut info),
+        Ok(f32::NEG_INFINITY)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 5)
+    )
+}
+
+#[test]
+fn test_decimal() {
+    use numbers::*;
+    let mut iter = "1_234.5".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        decimal_with::<String, NumberError>(NumberFormat::RUST)
+        .parse(&mut iter).record_to(&mut info),
+        Ok("1234.5".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(7, 8)
+    )
+}
+
+// Strings
+
+#[test]
+fn test_string_literal() {
+    use strings::*;
+    let mut iter = r#""a\"\n\x41\u{1F600}" 'b'"#.chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        string_literal::<StringError>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("a\"\nA😀".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(20, 20)
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        string_literal::<StringError>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(StringError::new(StringErrorKind::ExpectedQuote, 0))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        string_literal_with::<StringError>(StringFormat { quotes: vec!['"', '\''], ..StringFormat::default() })
+        .parse(&mut iter).record_to(&mut info),
+        Ok("b".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    )
+}
+
+#[test]
+fn test_string_literal_errors() {
+    use strings::*;
+    let mut iter = r#""ab\qc" "\x80" "abc"#.chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        string_literal::<StringError>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(StringError::new(StringErrorKind::UnknownEscape('q'), 3))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 5)
+    );
+    assert_eq!(
+        iter.next(),
+        Some('\\')
+    );
+
+    iter.nth(3);
+    info = ParseInfo::default();
+    assert_eq!(
+        string_literal::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("invalid escape at offset 1".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 5)
+    );
+
+    iter.nth(5);
+    info = ParseInfo::default();
+    assert_eq!(
+        string_literal::<StringError>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(StringError::new(StringErrorKind::Unterminated, 0))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 5)
+    )
+}
+
+#[test]
+fn test_literal_parse_errors() {
+    use errors::*;
+    use input::*;
+    type Error = ParseError<Location, String>;
+    let mut iter = Located::new("x 300 \"a".chars());
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        numbers::integer::<u8, Error>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::invalid("expected digits", "x".into(), Location::new(0, 0, 1, 1)))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    iter.next();
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        numbers::integer::<u8, Error>()
+        .parse(&mut iter).record_to(&mut info)
+        .map_err(|err| err.to_string()),
+        Err("number out of range at 1:3".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 4)
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    assert_eq!(
+        strings::string_literal::<Error>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::invalid("unterminated string literal", "\"a".into(), Location::new(6, 6, 1, 7)))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    );
+
+    // a bad escape is located at its escape char
+    let mut iter = Located::new(r#""abc\q" "\x8g""#.chars());
+    info = ParseInfo::default();
+    assert_eq!(
+        strings::string_literal::<Error>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::invalid("unknown escape `q` at offset 4", "\\q".into(), Location::new(4, 4, 1, 5)))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(4, 6)
+    );
+
+    iter.nth(3);
+    info = ParseInfo::default();
+    assert_eq!(
+        strings::string_literal::<Error>()
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::invalid("invalid escape at offset 1", "\\x8g".into(), Location::new(9, 9, 1, 10)))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 5)
+    )
+}
+
+#[test]
+fn test_raw_string_literal() {
+    use strings::*;
+    let format = StringFormat { raw: true, ..StringFormat::default() };
+    let mut iter = r###"r#"a"\n"#"b"r"###.chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        string_literal_with::<StringError>(format.clone())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(r#"a"\n"#.into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(9, 9)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        string_literal_with::<StringError>(format.clone())
+        .parse(&mut iter).record_to(&mut info),
+        Ok("b".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        string_literal_with::<StringError>(format)
+        .parse(&mut iter).record_to(&mut info),
+        Err(StringError::new(StringErrorKind::ExpectedQuote, 0))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 2)
+    )
+}
+
+// Input
+
+#[test]
+fn test_located() {
+    use input::*;
+    let mut iter = Located::new("aé\r\nb".chars());
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        literal::<String>("aé")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("aé".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 2)
+    );
+    assert_eq!(
+        iter.pos(),
+        Location::new(2, 3, 1, 3)
+    );
+
+    let backup = iter.clone();
+    info = ParseInfo::default();
+    assert_eq!(
+        text::newline::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok("\r\n")
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 2)
+    );
+    assert_eq!(
+        iter.pos(),
+        Location::new(4, 5, 2, 1)
+    );
+    assert_eq!(
+        backup.pos(),
+        Location::new(2, 3, 1, 3)
+    );
+
+    iter.next();
+    assert_eq!(
+        iter.pos(),
+        Location::new(5, 6, 2, 2)
+    )
+}
+
+#[test]
+fn test_position() {
+    use input::*;
+    let mut iter = Located::new(b"a\nb".iter());
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        apply!(
+            |_, _, pos| pos,
+            any_token::<String>(),
+            any_token::<String>(),
+            position::<String>()
+        )
+        .parse(&mut iter).record_to(&mut info),
+        Ok(Location::new(2, 2, 2, 1))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 2)
+    );
+    assert_eq!(
+        iter.location().to_string(),
+        "2:1"
+    )
+}
+
+#[test]
+fn test_spanned() {
+    use input::*;
+    let mut iter = Located::new("let\n  x".chars());
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        expect("let", "test_failure")
+        .spanned()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(("let".into(), Span::new(Location::new(0, 0, 1, 1), Location::new(3, 3, 1, 4))))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        Spanned::new(text::skip_whitespace::<&str>())
+        .parse(&mut iter).record_to(&mut info),
+        Ok(((), Span::new(Location::new(3, 3, 1, 4), Location::new(6, 6, 2, 3))))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 4)
+    );
+
+    info = ParseInfo::default();
+    let res = Spanned::new(text::identifier::<String>())
+        .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        res.map(|(name, span)| (name, span.len())),
+        Ok(("x".into(), 1))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 2)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        Spanned::new(literal::<String>("in"))
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected `in`, found end of input".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+// Recognition
+
+#[test]
+fn test_recognize() {
+    let mut iter = "12.5e3x".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        Recognize::<_, String>::new(numbers::float::<f64, numbers::NumberError>())
+        .parse(&mut iter).record_to(&mut info),
+        Ok("12.5e3".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 7)
+    );
+
+    let mut iter = [1, 2, 3].into_iter();
+
+    info = ParseInfo::default();
+    assert_eq!(
+        Recognize::<_, Vec<_>>::new(take_while::<_, !>(|n: &i32| *n < 3))
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec![1, 2])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    );
+
+    let mut iter = "abcd".chars();
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "test_failure")
+        .recognize::<Vec<char>>()
+        .parse(&mut iter).record_to(&mut info),
+        Ok(vec!['a', 'b', 'c'])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        expect("abc", "err")
+        .recognize::<String>()
+        .parse(&mut iter).record_to(&mut info),
+        Err("err".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 3)
+    )
+}
+
+#[test]
+fn test_recognize_slice() {
+    let source = String::from("größe = 1");
+    let mut iter = input::Located::new(source.chars());
+
+    let mut info = ParseInfo::default();
+    let name: Result<&str, _> = RecognizeSlice::new(text::identifier::<String>())
+        .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        name,
+        Ok("größe")
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(5, 6)
+    );
+    assert_eq!(
+        input::AsSlice::as_slice(&iter),
+        " = 1"
+    );
+
+    let tokens = [1, 1, 2];
+    let mut iter = tokens.iter();
+
+    info = ParseInfo::default();
+    assert_eq!(
+        RecognizeSlice::new(take_while::<_, !>(|n: &&i32| **n == 1))
+        .parse(&mut iter).record_to(&mut info),
+        Ok(&tokens[..2])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    )
+}
+
+#[test]
+fn test_str_input() {
+    let source = String::from("GET /größe 200");
+    let mut iter = input::StrInput::new(&source);
+
+    let mut info = ParseInfo::default();
+    let method: Result<&str, !> = RecognizeSlice::new(skip_while(|c: &char| c.is_ascii_uppercase()))
+        .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        method,
+        Ok("GET")
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 4)
+    );
+    assert_eq!(
+        iter.offset(),
+        3
+    );
+
+    iter.next();
+    info = ParseInfo::default();
+    let path: Result<&str, !> = RecognizeSlice::new(skip_while(|c: &char| !c.is_whitespace()))
+        .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        path,
+        Ok("/größe")
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(6, 7)
+    );
+    assert_eq!(
+        input::Positioned::pos(&iter),
+        12
+    );
+    assert_eq!(
+        iter.rest(),
+        " 200"
+    )
+}
+
+#[test]
+fn test_slice_input() {
+    let tokens = [1, 1, 2, 3];
+    let mut iter = input::SliceInput::new(&tokens);
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        RecognizeSlice::new(skip_while::<_, !>(|n: &&i32| **n == 1))
+        .parse(&mut iter).record_to(&mut info),
+        Ok(&tokens[..2])
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        token::<_, String>(&2)
+        .parse(&mut iter).record_to(&mut info),
+        Ok(&2)
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 1)
+    );
+    assert_eq!(
+        (iter.offset(), iter.rest()),
+        (3, &tokens[3..])
+    )
+}
+
+// Errors
+
+#[test]
+fn test_parse_error_display() {
+    use errors::*;
+
+    let err: ParseError<usize, String> = ParseError::expected_found("abc".into(), "abd".into(), 2);
+    assert_eq!(
+        err.to_string(),
+        "expected `abc`, found `abd` at 2"
+    );
+
+    let err: ParseError<usize, String> = ParseError::expected_found(")".into(), "".into(), 5);
+    assert_eq!(
+        err.to_string(),
+        "expected `)`, found end of input at 5"
+    );
+
+    let err: ParseError<usize, String> = ParseError::branching(
+        vec![
+            BranchEntryError::new("", "abc".into()),
+            BranchEntryError::new("", "(".into()),
+            BranchEntryError::new("number", "0".into()),
+        ],
+        "x".into(),
+        0
+    );
+    assert_eq!(
+        err.to_string(),
+        "expected one of `abc`, `(`, number, found `x` at 0"
+    );
+
+    let err = ParseError::contextualised(
+        "function body",
+        ParseError::contextualised("call", err, 3),
+        1
+    );
+    assert_eq!(
+        err.to_string(),
+        "expected one of `abc`, `(`, number, found `x` at 0, in call at 3, in function body at 1"
+    );
+    assert_eq!(
+        err.pos(),
+        Some(&0)
+    )
+}
+
+#[test]
+fn test_parse_error_merge() {
+    use errors::*;
+
+    let abc: ParseError<usize, String> = ParseError::expected_found("abc".into(), "x".into(), 4);
+    let paren: ParseError<usize, String> = ParseError::expected_found("(".into(), "x".into(), 4);
+    let early: ParseError<usize, String> = ParseError::expected_found("1".into(), "y".into(), 2);
+
+    assert_eq!(
+        early.clone().merge(abc.clone()),
+        abc
+    );
+    assert_eq!(
+        abc.clone().merge(early.clone()),
+        abc
+    );
+
+    let merged = abc.clone()
+        .merge(paren.clone())
+        .merge(abc.clone())
+        .merge(early);
+    assert_eq!(
+        merged,
+        ParseError::branching(
+            vec![
+                BranchEntryError::new("", "abc".into()),
+                BranchEntryError::new("", "(".into()),
+            ],
+            "x".into(),
+            4
+        )
+    );
+
+    let context = ParseError::contextualised("call", paren.clone(), 0);
+    assert_eq!(
+        abc.clone().merge(context.clone()).merge(context.clone()),
+        ParseError::bundle(vec![abc, context])
+    )
+}
+
+#[test]
+fn test_parser_error() {
+    use errors::*;
+    use input::*;
+    let mut iter = Located::new("let x\n= *".chars());
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        literal::<ParseError<Location, String>>("let")
+        .parse(&mut iter).record_to(&mut info),
+        Ok("let".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 3)
+    );
+
+    info = ParseInfo::default();
+    let err = literal::<ParseError<Location, String>>(" y")
+        .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        err,
+        Err(ParseError::expected_found(" y".into(), "x".into(), Location::new(4, 4, 1, 5)))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 2)
+    );
+
+    let mut iter = Located::new("let x\n= *".chars());
+    info = ParseInfo::default();
+    let err = apply!(
+        |_, c| c,
+        tag::<_, ParseError<Location, String>>("let x\n= ".chars()),
+        one_of("0123456789(".chars())
+    )
+    .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        err.map_err(|err| err.to_string()),
+        Err("expected one of `0`, `1`, `2`, `3`, `4`, `5`, `6`, `7`, `8`, `9`, `(`, found `*` at 2:3".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(8, 9)
+    );
+
+    info = ParseInfo::default();
+    let err = end_of_input::<String>()
+        .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        err,
+        Err("unexpected `*`".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_or_merge() {
+    use errors::*;
+    use input::*;
+    type Error = ParseError<Location, String>;
+    let mut iter = Located::new("x".chars());
+
+    let mut info = ParseInfo::default();
+    let err = OrMerge::new(
+        OrMerge::new(literal::<Error>("abc"), literal("(")),
+        literal("abc")
+    )
+    .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        err,
+        Err(ParseError::branching(
+            vec![
+                BranchEntryError::new("", "abc".into()),
+                BranchEntryError::new("", "(".into()),
+            ],
+            "x".into(),
+            Location::default()
+        ))
+    );
+    assert_eq!(
+        err.map_err(|err| err.to_string()),
+        Err("expected one of `abc`, `(`, found `x` at 1:1".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    let mut iter = Located::new("ab)".chars());
+    info = ParseInfo::default();
+    assert_eq!(
+        OrMerge::new(Attempt::new(literal::<Error>("abc")), literal("("))
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::expected_found("abc".into(), ")".into(), Location::new(2, 2, 1, 3)))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 3)
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        OrMerge::new(literal::<Error>("("), literal("ab"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("ab".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 2)
+    );
+
+    // tokens are kept as they are, whatever they contain
+    let mut iter = Located::new("x".chars());
+    info = ParseInfo::default();
+    assert_eq!(
+        OrMerge::new(literal::<Error>("a, b"), literal(", found "))
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::branching(
+            vec![
+                BranchEntryError::new("", "a, b".into()),
+                BranchEntryError::new("", ", found ".into()),
+            ],
+            "x".into(),
+            Location::default()
+        ))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    )
+}
+
+#[test]
+fn test_furthest() {
+    use errors::*;
+    use input::*;
+    type Error = ParseError<Location, String>;
+    let mut iter = Furthest::new(Located::new("abd".chars()));
+
+    let mut info = ParseInfo::default();
+    let err = OrCompose::new(Attempt::new(literal::<Error>("abc")), literal("x"))
+        .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        err,
+        Err(ParseError::expected_found("x".into(), "a".into(), Location::default()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 3)
+    );
+    assert_eq!(
+        iter.furthest(),
+        Some(ParseError::expected_found("abc".into(), "d".into(), Location::new(2, 2, 1, 3)))
+    );
+    assert_eq!(
+        iter.deepest(err.unwrap_err()).to_string(),
+        "expected `abc`, found `d` at 1:3"
+    );
+
+    info = ParseInfo::default();
+    assert_eq!(
+        Backtrack::new(literal::<Error>("abd"))
+        .parse(&mut iter).record_to(&mut info),
+        Ok("abd".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 3)
+    );
+    assert_eq!(
+        Positioned::pos(&iter),
+        Location::default()
+    );
+
+    // errors from lexing numbers are recorded too
+    let mut iter = Furthest::new(Located::new("(300".chars()));
+    info = ParseInfo::default();
+    assert_eq!(
+        OrCompose::new(
+            Attempt::new(Map::new(seq((token::<_, Error>('('), numbers::integer::<u8, Error>())), |(_, n): (char, u8)| n.to_string())),
+            literal("x")
+        )
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::expected_found("x".into(), "(".into(), Location::default()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 5)
+    );
+    assert_eq!(
+        iter.furthest(),
+        Some(ParseError::invalid("number out of range", "300".into(), Location::new(1, 1, 1, 2)))
+    )
+}
+
+#[test]
+fn test_furthest_under_located() {
+    use errors::*;
+    use input::*;
+    type Error = ParseError<Location, String>;
+    let furthest = Furthest::new("abd".chars());
+    let mut iter = Located::new(furthest.clone());
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        OrCompose::new(Attempt::new(literal::<Error>("abc")), literal("x"))
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::expected_found("x".into(), "a".into(), Location::default()))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 3)
+    );
+    assert_eq!(
+        furthest.furthest(),
+        Some(ParseError::expected_found("abc".into(), "d".into(), Location::new(2, 2, 1, 3)))
+    )
+}
+
+#[test]
+fn test_label() {
+    use errors::*;
+    use input::*;
+    type Error = ParseError<Location, String>;
+    let mut iter = "+".chars();
+
+    let mut info = ParseInfo::default();
+    assert_eq!(
+        Label::new(text::ascii_digits::<String>(), "number".into())
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected number, found `+`".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    let mut iter = Located::new("9".chars());
+    info = ParseInfo::default();
+    let err = OrMerge::new(
+        Label::new(literal::<Error>("let"), "keyword".into()),
+        Label::new(text::identifier(), "identifier".into())
+    )
+    .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        err.map_err(|err| err.to_string()),
+        Err("expected one of keyword, identifier, found `9` at 1:1".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 1)
+    );
+
+    let mut iter = Located::new("abd".chars());
+    info = ParseInfo::default();
+    assert_eq!(
+        Label::new(literal::<Error>("abc"), "keyword".into())
+        .parse(&mut iter).record_to(&mut info),
+        Err(ParseError::expected_found("abc".into(), "d".into(), Location::new(2, 2, 1, 3)))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    )
+}
+
+#[test]
+fn test_context() {
+    use errors::*;
+    use input::*;
+    type Error = ParseError<Location, String>;
+    let mut iter = Located::new("f(x;".chars());
+
+    let mut info = ParseInfo::default();
+    let err = Context::new(
+        apply!(
+            |_, args| args,
+            literal::<Error>("f"),
+            Context::new(
+                apply!(
+                    |_, x, _| x,
+                    literal::<Error>("("),
+                    text::identifier(),
+                    literal(")")
+                ),
+                "call".into()
+            )
+        ),
+        "function body".into()
+    )
+    .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        err.clone().map_err(|err| err.to_string()),
+        Err("expected `)`, found `;` at 1:4, in call at 1:2, in function body at 1:1".into())
+    );
+    assert_eq!(
+        err.map_err(|err| err.pos().cloned()),
+        Err(Some(Location::new(3, 3, 1, 4)))
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(3, 4)
+    );
+
+    let mut iter = "f(x;".chars();
+    info = ParseInfo::default();
+    assert_eq!(
+        Context::new(
+            apply!(
+                |_, _| (),
+                literal::<String>("f("),
+                literal("y")
+            ),
+            "call".into()
+        )
+        .parse(&mut iter).record_to(&mut info),
+        Err("expected `y`, found `x`, in call".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(2, 3)
+    )
+}
+
+#[test]
+fn test_suggest() {
+    use errors::*;
+    use input::*;
+    type Error = ParseError<Location, String>;
+    let keywords: Vec<String> = vec!["return".into(), "let".into()];
+    let mut iter = Located::new("retrun x".chars());
+
+    let mut info = ParseInfo::default();
+    let err = Suggest::new(
+        OrMerge::new(Attempt::new(literal::<Error>("return")), Attempt::new(literal("let"))),
+        text::identifier::<Error>()
+    )
+        .parse(&mut iter).record_to(&mut info);
+    assert_eq!(
+        err,
+        Err(ParseError::suggestion(
+            ParseError::expected_found("return".into(), "retrun".into(), Location::default()),
+            "return".into()
+        ))
+    );
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "expected `return`, found `retrun` at 1:1, did you mean `return`?"
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 7)
+    );
+
+    // too far from any keyword to be a typo
+    let mut iter = Located::new("x = 1".chars());
+    info = ParseInfo::default();
+    assert_eq!(
+        Suggest::new(
+            OrMerge::new(literal::<Error>("return"), literal("let")),
+            text::identifier::<Error>()
+        )
+        .parse(&mut iter).record_to(&mut info)
+        .map_err(|err| err.to_string()),
+        Err("expected one of `return`, `let`, found `x` at 1:1".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(0, 2)
+    );
+
+    // the whole word is named, not only where the keyword stopped matching
+    let mut iter = Located::new("lte".chars());
+    info = ParseInfo::default();
+    assert_eq!(
+        Suggest::new(
+            OrMerge::new(literal::<Error>("return"), literal("let")),
+            text::identifier::<Error>()
+        )
+        .parse(&mut iter).record_to(&mut info)
+        .map_err(|err| err.to_string()),
+        Err("expected `let`, found `lte` at 1:1, did you mean `let`?".into())
+    );
+    assert_eq!(
+        info,
+        ParseInfo::new(1, 4)
+    );
+
+    assert_eq!(edit_distance("retrun", "return"), 1);
+    assert_eq!(edit_distance("", "let"), 3);
+    assert_eq!(
+        ParseError::<usize, String>::bundle(vec![
+            ParseError::branching(vec![BranchEntryError::new("", "if".into()), BranchEntryError::new("expression", "".into())], "x".into(), 0),
+            ParseError::contextualised("block", ParseError::expected_found("{".into(), "x".into(), 0), 0),
+        ]).candidates(),
+        vec!["if".to_string(), "{".to_string()]
+    );
+    assert_eq!(
+        ParseError::<usize, String>::branching(
+            vec![BranchEntryError::new("", "a, b".into()), BranchEntryError::new("", "c".into())],
+            "x".into(),
+            0
+        ).candidates(),
+        vec!["a, b".to_string(), "c".to_string()]
+    );
+    assert_eq!(closest("lett", keywords.iter().map(String::as_str)), Some("let"));
+    assert_eq!(closest("let", keywords.iter().map(String::as_str)), None)
+}
+
+// Reports
+
+#[test]
+fn test_report() {
+    use errors::*;
+    use input::*;
+    use report::*;
+    let source = "fn main() {\n\tf(x;\n}";
+    let err: ParseError<Location, String> = ParseError::contextualised(
+        "function body",
+        ParseError::contextualised(
+            "call",
+            ParseError::expected_found(")".into(), ";".into(), Location::new(15, 15, 2, 4)),
+            Location::new(12, 12, 2, 1)
+        ),
+        Location::new(10, 10, 1, 11)
+    );
+
+    let mut report = Report::new(source);
+    report.file = Some("main.dsl");
+    assert_eq!(
+        report.render(&err),
+        concat!(
+            "error: expected `)`, found `;`\n",
+            " --> main.dsl:2:4\n",
+            "  |\n",
+            "1 | fn main() {\n",
+            "  |           - in function body\n",
+            "2 | \tf(x;\n",
+            "  | \t  ^ expected `)`\n",
+            "  | - in call\n",
+        )
+    );
+
+    let err: ParseError<Location, String> = ParseError::bundle(vec![
+        ParseError::branching(vec![], "".into(), Location::new(18, 18, 3, 2)),
+        ParseError::expected_found("fn".into(), "main".into(), Location::new(3, 3, 1, 4)),
+    ]);
+    report.file = None;
+    assert_eq!(
+        report.render(&err),
+        concat!(
+            "error: unexpected end of input\n",
+            " --> 3:2\n",
+            "  |\n",
+            "3 | }\n",
+            "  |  ^\n",
+            "\n",
+            "error: expected `fn`, found `main`\n",
+            " --> 1:4\n",
+            "  |\n",
+            "1 | fn main() {\n",
+            "  |    ^^^^ expected `fn`\n",
+        )
+    );
+
+    let err: ParseError<Location, String> = ParseError::contextualised(
+        "function body",
+        ParseError::bundle(vec![
+            ParseError::expected_found(")".into(), ";".into(), Location::new(15, 15, 2, 4)),
+            ParseError::branching(vec![], "".into(), Location::new(18, 18, 3, 2)),
+        ]),
+        Location::new(10, 10, 1, 11)
+    );
+    assert_eq!(
+        report.render(&err),
+        concat!(
+            "error: expected `)`, found `;`\n",
+            " --> 2:4\n",
+            "  |\n",
+            "1 | fn main() {\n",
+            "  |           - in function body\n",
+            "2 | \tf(x;\n",
+            "  | \t  ^ expected `)`\n",
+            "\n",
+            "error: unexpected end of input\n",
+            " --> 3:2\n",
+            "  |\n",
+            "1 | fn main() {\n",
+            "  |           - in function body\n",
+            "3 | }\n",
+            "  |  ^\n",
+        )
+    );
+
+    // a location that was never advanced past line or column zero still renders
+    assert_eq!(
+        report.render(&ParseError::<Location, String>::expected_found("a".into(), "b".into(), Location::new(0, 0, 0, 0))),
+        concat!(
+            "error: expected `a`, found `b`\n",
+            " --> 0:0\n",
+            "  |\n",
+            "0 | fn main() {\n",
+            "  | ^ expected `a`\n",
+        )
+    );
+
+    report.colour = true;
+    assert_eq!(
+        report.render(&ParseError::<Location, String>::expected_found("a".into(), "b".into(), Location::default())),
+        "\x1b[1;31merror:\x1b[0m\x1b[1m expected `a`, found `b`\x1b[0m\n \x1b[1;34m-->\x1b[0m 1:1\n  \x1b[1;34m|\x1b[0m\n\x1b[1;34m1\x1b[0m \x1b[1;34m|\x1b[0m fn main() {\n  \x1b[1;34m|\x1b[0m \x1b[1;31m^ expected `a`\x1b[0m\n"
+    )
+}
+
+#[test]
+fn test_export() {
+    use errors::*;
+    use export::*;
+    use input::*;
+    use report::Severity;
+    let err: ParseError<Location, String> = ParseError::contextualised(
+        "call",
+        ParseError::expected_found(")".into(), "\"a\"".into(), Location::new(4, 4, 1, 5)),
+        Location::new(1, 1, 1, 2)
+    );
+    let eof: ParseError<Location, String> = ParseError::branching(
+        vec![BranchEntryError::new("", "x".into()), BranchEntryError::new("expression", "".into())],
+        "".into(),
+        Location::new(7, 7, 2, 1)
+    );
+
+    assert_eq!(
+        to_json([(Severity::Error, &err), (Severity::Warning, &eof)]),
+        concat!(
+            r#"{"version":1,"diagnostics":["#,
+            r#"{"severity":"error","message":"expected `)`, found `\"a\"`","#,
+            r#""span":{"start":{"index":4,"offset":4,"line":1,"column":5},"end":{"index":7,"offset":7,"line":1,"column":8}},"#,
+            r#""expected":[")"],"found":"\"a\"","context":[{"label":"call","start":{"index":1,"offset":1,"line":1,"column":2}}]},"#,
+            r#"{"severity":"warning","message":"expected one of `x`, expression, found end of input","#,
+            r#""span":{"start":{"index":7,"offset":7,"line":2,"column":1},"end":{"index":7,"offset":7,"line":2,"column":1}},"#,
+            r#""expected":["x","expression"],"found":null,"context":[]}"#,
+            r#"]}"#,
+        )
+    );
+
+    assert_eq!(
+        to_sarif([(Severity::Error, &ParseError::bundle(vec![err.clone()]))], "lint", Some("main.dsl")),
+        concat!(
+            r#"{"version":"2.1.0","$schema":"https://json.schemastore.org/sarif-2.1.0.json","runs":[{"tool":{"driver":{"name":"lint"}},"results":["#,
+            r#"{"level":"error","message":{"text":"expected `)`, found `\"a\"`"},"#,
+            r#""locations":[{"physicalLocation":{"artifactLocation":{"uri":"main.dsl"},"region":{"startLine":1,"startColumn":5,"endLine":1,"endColumn":8,"byteOffset":4,"byteLength":3}}}],"#,
+            r#""relatedLocations":[{"physicalLocation":{"artifactLocation":{"uri":"main.dsl"},"region":{"startLine":1,"startColumn":2,"endLine":1,"endColumn":2,"byteOffset":1,"byteLength":0}},"message":{"text":"in call"}}]}"#,
+            r#"]}]}"#,
+        )
+    );
+
+    // each error of a bundle keeps the contexts it was found in
+    let nested: ParseError<Location, String> = ParseError::contextualised(
+        "block",
+        ParseError::bundle(vec![err, ParseError::suggestion(eof, "x".into())]),
+        Location::default()
+    );
+    assert_eq!(
+        to_json([(Severity::Error, &nested)]),
+        concat!(
+            r#"{"version":1,"diagnostics":["#,
+            r#"{"severity":"error","message":"expected `)`, found `\"a\"`","#,
+            r#""span":{"start":{"index":4,"offset":4,"line":1,"column":5},"end":{"index":7,"offset":7,"line":1,"column":8}},"#,
+            r#""expected":[")"],"found":"\"a\"","context":["#,
+            r#"{"label":"call","start":{"index":1,"offset":1,"line":1,"column":2}},"#,
+            r#"{"label":"block","start":{"index":0,"offset":0,"line":1,"column":1}}]},"#,
+            r#"{"severity":"error","message":"expected one of `x`, expression, found end of input, did you mean `x`?","#,
+            r#""span":{"start":{"index":7,"offset":7,"line":2,"column":1},"end":{"index":7,"offset":7,"line":2,"column":1}},"#,
+            r#""expected":["x","expression"],"found":null,"context":["#,
+            r#"{"label":"block","start":{"index":0,"offset":0,"line":1,"column":1}}]}"#,
+            r#"]}"#,
+        )
+    )
+}
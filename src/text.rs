@@ -0,0 +1,320 @@
+use std::marker::PhantomData;
+use super::*;
+use errors::{FromInvalid, ParserError};
+
+pub type CharPredicate = fn(&char) -> bool;
+pub type CharRun<E> = Map<TakeWhileMN<CharPredicate, E>, fn(Vec<char>) -> String>;
+pub type SkipChars<E> = Map<TakeWhile<CharPredicate, E>, fn(Vec<char>)>;
+
+// the usual groups to keep balanced when skipping, see `skip_balanced`
+pub const BRACKETS: [(char, char); 3] = [('(', ')'), ('[', ']'), ('{', '}')];
+
+// the zero of each run of ten decimal digits (general category Nd) as of Unicode 15.0
+const DECIMAL_ZEROS: [u32; 68] = [
+    0x0030, 0x0660, 0x06F0, 0x07C0, 0x0966, 0x09E6, 0x0A66, 0x0AE6,
+    0x0B66, 0x0BE6, 0x0C66, 0x0CE6, 0x0D66, 0x0DE6, 0x0E50, 0x0ED0,
+    0x0F20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80,
+    0x1A90, 0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900,
+    0xA9D0, 0xA9F0, 0xAA50, 0xABF0, 0xFF10, 0x104A0, 0x10D30, 0x11066,
+    0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0, 0x11650, 0x116C0,
+    0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x11F50, 0x16A60,
+    0x16AC0, 0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140,
+    0x1E2F0, 0x1E4F0, 0x1E950, 0x1FBF0,
+];
+
+pub fn is_decimal_digit(c: &char) -> bool {
+    let c = *c as u32;
+    let run = DECIMAL_ZEROS.partition_point(|&zero| zero <= c);
+    run > 0 && c - DECIMAL_ZEROS[run - 1] < 10
+}
+
+// Character classes
+
+pub fn ascii_digit<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(char::is_ascii_digit)
+}
+
+pub fn hex_digit<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(char::is_ascii_hexdigit)
+}
+
+// decimal digits in any script, not other numeric chars such as '½' or 'Ⅻ'
+pub fn digit<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(is_decimal_digit)
+}
+
+pub fn ascii_alphabetic<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(char::is_ascii_alphabetic)
+}
+
+pub fn alphabetic<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(|c| c.is_alphabetic())
+}
+
+pub fn ascii_alphanumeric<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(char::is_ascii_alphanumeric)
+}
+
+pub fn alphanumeric<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(|c| c.is_alphanumeric())
+}
+
+pub fn ascii_whitespace<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(char::is_ascii_whitespace)
+}
+
+pub fn whitespace<E>() -> Satisfy<CharPredicate, E> {
+    satisfy(|c| c.is_whitespace())
+}
+
+// never fails, use `whitespace` to require at least one
+pub fn skip_whitespace<E>() -> SkipChars<E> {
+    Map::new(take_while(|c| c.is_whitespace()), |_| ())
+}
+
+// Runs
+
+pub fn ascii_digits<E>() -> CharRun<E> {
+    Map::new(take_while1(char::is_ascii_digit), String::from_iter)
+}
+
+pub fn alphabetics<E>() -> CharRun<E> {
+    Map::new(take_while1(|c| c.is_alphabetic()), String::from_iter)
+}
+
+pub fn alphanumerics<E>() -> CharRun<E> {
+    Map::new(take_while1(|c| c.is_alphanumeric()), String::from_iter)
+}
+
+// Lines
+
+// matches "\n" or "\r\n", a lone '\r' is not a newline
+#[derive(Clone)]
+pub struct Newline<E> {
+    _e: PhantomData<E>,
+}
+
+impl<E> Newline<E> {
+    pub fn new() -> Newline<E> {
+        Newline {
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<E> Default for Newline<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I, E> Parser<I> for Newline<E> where
+    I: Iterator<Item=char> + Clone,
+    E: ParserError<I>
+{
+    type Value = &'static str;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<&'static str, E> {
+        let mut ahead = iter.clone();
+        match ahead.next() {
+            Some('\n') => {
+                *iter = ahead;
+                ParseInfo::new(1, 1).ok("\n")
+            },
+            Some('\r') => match next_if(&mut ahead, |c| *c == '\n') {
+                Ok(_) => {
+                    *iter = ahead;
+                    ParseInfo::new(2, 2).ok("\r\n")
+                },
+                // a lone '\r' is what is wrong, reported where it is
+                Err(_) => ParseInfo::new(0, 2).err(E::unexpected('\r', iter)),
+            },
+            found => ParseInfo::new(0, 1).err(reject(vec!['\n'], found, iter)),
+        }
+    }
+}
+
+pub fn newline<E>() -> Newline<E> {
+    Newline::new()
+}
+
+// Identifiers
+
+// a Unicode XID identifier, additionally allowing a leading '_' as Rust does
+#[derive(Clone)]
+pub struct Identifier<E> {
+    _e: PhantomData<E>,
+}
+
+impl<E> Identifier<E> {
+    pub fn new() -> Identifier<E> {
+        Identifier {
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<E> Default for Identifier<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn is_identifier_start(c: &char) -> bool {
+    *c == '_' || unicode_ident::is_xid_start(*c)
+}
+
+pub fn is_identifier_continue(c: &char) -> bool {
+    unicode_ident::is_xid_continue(*c)
+}
+
+impl<I, E> Parser<I> for Identifier<E> where
+    I: Iterator<Item=char> + Clone,
+    E: ParserError<I>
+{
+    type Value = String;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<String, E> {
+        let mut info = ParseInfo::default();
+        let res = satisfy(is_identifier_start)
+            .parse(iter).record_to(&mut info)
+            .map(|start| {
+                let Ok(rest) = take_while::<_, std::convert::Infallible>(is_identifier_continue)
+                    .parse(iter).record_to(&mut info);
+                std::iter::once(start).chain(rest).collect()
+            });
+        info.with(res)
+    }
+}
+
+pub fn identifier<E>() -> Identifier<E> {
+    Identifier::new()
+}
+
+// Case insensitivity
+
+// compares chars by their lowercase mappings, returning the input as it was written
+#[derive(Clone)]
+pub struct LiteralNoCase<E> {
+    literal: String,
+    _e: PhantomData<E>,
+}
+
+impl<E> LiteralNoCase<E> {
+    pub fn new(literal: String) -> LiteralNoCase<E> {
+        LiteralNoCase {
+            literal,
+            _e: PhantomData,
+        }
+    }
+}
+
+impl<I, E> Parser<I> for LiteralNoCase<E> where
+    I: Iterator<Item=char> + Clone,
+    E: ParserError<I>
+{
+    type Value = String;
+    type Error = E;
+
+    fn parse(&self, iter: &mut I) -> ParseResult<String, E> {
+        let mut info = ParseInfo::default();
+        let mut found = String::new();
+        for expected in self.literal.chars() {
+            match next_if(iter, |c| c.to_lowercase().eq(expected.to_lowercase())) {
+                Ok(c) => {
+                    info += ParseInfo::new(1, 1);
+                    found.push(c)
+                },
+                Err(token) => {
+                    info += ParseInfo::new(0, 1);
+                    return info.err(reject(self.literal.chars().collect(), token, iter));
+                },
+            }
+        }
+        info.ok(found)
+    }
+}
+
+pub fn literal_no_case<E>(literal: impl Into<String>) -> LiteralNoCase<E> {
+    LiteralNoCase::new(literal.into())
+}
+
+// Lexing
+
+// errors found while scanning a lexeme, see `Scan::finish`
+pub(crate) trait LexError {
+    // where the error lies within the lexeme as an offset and a number of tokens, given what was scanned
+    // no tokens means the one at the offset
+    fn span(&self, info: &ParseInfo) -> (usize, usize);
+}
+
+// accumulates the text of a lexeme alongside its `ParseInfo`, for parsers that decode as they go
+#[derive(Clone)]
+pub(crate) struct Scan<I> {
+    pub(crate) iter: I,
+    pub(crate) info: ParseInfo,
+    pub(crate) text: String,
+}
+
+impl<I> Scan<I> where
+    I: Iterator<Item=char> + Clone
+{
+    pub(crate) fn new(iter: I) -> Scan<I> {
+        Scan {
+            iter,
+            info: ParseInfo::default(),
+            text: String::new(),
+        }
+    }
+
+    pub(crate) fn eat<F>(&mut self, accept: F) -> Option<char> where
+        F: FnOnce(&char) -> bool
+    {
+        match next_if(&mut self.iter, accept) {
+            Ok(c) => {
+                self.info += ParseInfo::new(1, 1);
+                Some(c)
+            },
+            Err(_) => {
+                self.info += ParseInfo::new(0, 1);
+                None
+            },
+        }
+    }
+
+    // only keeps what `f` scanned if it returns true
+    pub(crate) fn attempt<F>(&mut self, f: F) -> bool where
+        F: FnOnce(&mut Scan<I>) -> bool
+    {
+        let mut sub = Scan::new(self.iter.clone());
+        if f(&mut sub) {
+            self.iter = sub.iter;
+            self.info += sub.info;
+            self.text += &sub.text;
+            true
+        } else {
+            self.info += ParseInfo::new(0, sub.info.read);
+            false
+        }
+    }
+
+    // errors are located within the lexeme by `LexError::span`
+    pub(crate) fn finish<T, F, E>(self, iter: &mut I, res: Result<T, F>) -> ParseResult<T, E> where
+        F: LexError,
+        E: FromInvalid<F, I>
+    {
+        let res = res.map_err(|err| {
+            let (start, len) = err.span(&self.info);
+            let mut at = iter.clone();
+            for _ in 0..start {
+                at.next();
+            }
+            let found = at.clone().take(std::cmp::max(len, 1)).collect();
+            E::from_invalid(err, found, &at)
+        });
+        *iter = self.iter;
+        self.info.with(res)
+    }
+}